@@ -0,0 +1,243 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states created by repeatedly stepping a start state.
+///
+/// The states from step `prefix` and on repeat every `period` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    /// The number of steps before the first state of the cycle.
+    pub prefix: usize,
+    /// The number of states in the cycle.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Get the first step with the same state as step `n`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let cycle = Cycle { prefix: 3, period: 4 };
+    ///
+    /// assert_eq!(cycle.reduce(2), 2);
+    /// assert_eq!(cycle.reduce(7), 3);
+    /// assert_eq!(cycle.reduce(1_000_000_000), 4);
+    /// ```
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+/// A cycle together with all the states up to the end of its first repetition.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CycleHistory<S> {
+    /// The found cycle.
+    pub cycle: Cycle,
+    /// The states of the first `prefix + period` steps, starting with the start state.
+    pub states: Vec<S>,
+}
+
+impl<S> CycleHistory<S> {
+    /// Get the state after `n` steps.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let history = find_cycle(3u32, |&n| (n * n + 1) % 10);
+    ///
+    /// assert_eq!(*history.state_at(4), 5);
+    /// assert_eq!(*history.state_at(1_000_000_000), 5);
+    /// ```
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+/// Find the cycle of the states created by repeatedly calling `step`,
+/// remembering all the states on the way.
+///
+/// Each state is only created once, but all states are kept in memory.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// // 3, 0, 1, 2, 5, 6, 7, 0, 1, ...
+/// let history = find_cycle(3u32, |&n| (n * n + 1) % 10);
+///
+/// assert_eq!(history.cycle, Cycle { prefix: 1, period: 6 });
+/// assert_eq!(history.states, vec![3, 0, 1, 2, 5, 6, 7]);
+/// ```
+pub fn find_cycle<S, F>(start: S, mut step: F) -> CycleHistory<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            let cycle = Cycle {
+                prefix,
+                period: states.len() - prefix,
+            };
+            return CycleHistory { cycle, states };
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Find the cycle of the states created by repeatedly calling `step`
+/// with Brent's algorithm.
+///
+/// Only a few states are kept in memory, and the states don't have to be hashable,
+/// but `step` gets called more times than by `find_cycle`.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let cycle = brent(3u32, |&n| (n * n + 1) % 10);
+///
+/// assert_eq!(cycle, Cycle { prefix: 1, period: 6 });
+/// ```
+pub fn brent<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    Cycle { prefix, period }
+}
+
+/// Find the cycle of the states created by repeatedly calling `step`
+/// with Floyd's algorithm.
+///
+/// Like `brent`, but usually calls `step` more times.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let cycle = floyd(3u32, |&n| (n * n + 1) % 10);
+///
+/// assert_eq!(cycle, Cycle { prefix: 1, period: 6 });
+/// ```
+pub fn floyd<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { prefix, period }
+}
+
+/// Get the state after `n` steps, by only stepping `cycle.reduce(n)` times.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let step = |&n: &u32| (n * n + 1) % 10;
+/// let cycle = brent(3, step);
+///
+/// assert_eq!(state_at(3, step, &cycle, 1_000_000_000), 5);
+/// ```
+pub fn state_at<S, F>(start: S, mut step: F, cycle: &Cycle, n: usize) -> S
+where
+    F: FnMut(&S) -> S,
+{
+    let mut state = start;
+    for _ in 0..cycle.reduce(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Map;
+
+    #[test]
+    fn test_detectors_agree() {
+        for modulo in 2..200u64 {
+            for start in 0..modulo {
+                let step = |&n: &u64| (n * n + 1) % modulo;
+                let history = find_cycle(start, step);
+                assert_eq!(brent(start, step), history.cycle);
+                assert_eq!(floyd(start, step), history.cycle);
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_cycle() {
+        let map = Map::<i32>::from_string("#..\n...\n");
+        let history = find_cycle(map.clone(), |map| map.rotate_right());
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                prefix: 0,
+                period: 4
+            }
+        );
+        assert_eq!(history.state_at(1_000_000_001), &map.rotate_right());
+    }
+}
@@ -2,12 +2,18 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+/// module for finding cycles in repeated simulations
+mod cycle;
 /// module for graphs
 mod graph;
+/// module for generic shortest path searches
+mod search;
 /// module for maps, 2d points and directions
 mod world;
 
 pub const ALPHANUMS: &[u8; 62] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
 
+pub use cycle::*;
 pub use graph::*;
+pub use search::*;
 pub use world::*;
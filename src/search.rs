@@ -0,0 +1,447 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use num::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// A struct to keep a search state together with a cost.
+///
+/// Used to keep track of states and a cost, useful when storing them in BinaryHeap etc
+/// for search algorithms.
+///
+/// `Ord` and `PartialOrd` are implemented for it. Only the cost field is compared,
+/// and the lowest cost is the greatest.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let point = Point {x: 1, y: 3};
+/// let sc1 = StateAndCost{cost: 3, state: (point, Dir::North)};
+/// let sc2 = StateAndCost{cost: 7, state: (point, Dir::East)};
+/// assert!(sc2.cmp(&sc1) == std::cmp::Ordering::Less);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct StateAndCost<S, C> {
+    /// The cost to reach the state.
+    pub cost: C,
+    /// The search state.
+    pub state: S,
+}
+
+impl<S: Eq, C: Ord> Ord for StateAndCost<S, C> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl<S: Eq, C: Ord> PartialOrd for StateAndCost<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A path through the states of a search.
+///
+/// `states` starts with the start state and ends with the goal state.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StatePath<S, C> {
+    /// The total cost of the path.
+    pub cost: C,
+    /// The states along the path.
+    pub states: Vec<S>,
+}
+
+/// All the cheapest paths found by `dijkstra_all`.
+///
+/// The paths are kept as a graph where each state on them
+/// knows all the states it can be reached from at the lowest cost.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AllPaths<S: Eq + Hash, C> {
+    /// The total cost of each of the paths.
+    pub cost: C,
+    /// The goal states reached at the lowest cost.
+    pub goals: Vec<S>,
+    /// The cheapest previous states for each reached state.
+    pub parents: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C> AllPaths<S, C> {
+    /// Count the number of distinct cheapest paths.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// // Reach 4 from 0 by adding one or two, every step costs one.
+    /// let outcome = dijkstra_all(0u32, |&n| [(n + 1, 1), (n + 2, 1)], |&n| n == 4);
+    /// let paths = outcome.path.expect("Paths");
+    /// assert_eq!(paths.cost, 2);
+    /// assert_eq!(paths.count(), num::BigUint::from(1u32));
+    /// ```
+    pub fn count(&self) -> BigUint {
+        let mut counts: HashMap<&S, BigUint> = HashMap::new();
+        let mut stack: Vec<&S> = self.goals.iter().collect();
+        while let Some(&state) = stack.last() {
+            if counts.contains_key(state) {
+                stack.pop();
+                continue;
+            }
+            let parents = self.parents.get(state).map_or(&[][..], Vec::as_slice);
+            let missing: Vec<_> = parents
+                .iter()
+                .filter(|parent| !counts.contains_key(parent))
+                .collect();
+            if missing.is_empty() {
+                let count = if parents.is_empty() {
+                    One::one()
+                } else {
+                    parents.iter().map(|parent| &counts[parent]).sum()
+                };
+                counts.insert(state, count);
+                stack.pop();
+            } else {
+                stack.extend(missing);
+            }
+        }
+        self.goals.iter().map(|goal| &counts[goal]).sum()
+    }
+
+    /// Returns all states that are on any of the cheapest paths.
+    pub fn states(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut to_visit = self.goals.clone();
+        while let Some(state) = to_visit.pop() {
+            for parent in self.parents.get(&state).into_iter().flatten() {
+                if states.insert(parent.clone()) {
+                    to_visit.push(parent.clone());
+                }
+            }
+        }
+        states
+    }
+}
+
+/// The result of a search.
+///
+/// `path` is None when the goal couldn't be reached, which is different from
+/// a path with zero cost.
+/// `expanded` is the number of nodes the search expanded, useful for diagnostics.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchOutcome<P> {
+    /// The found path, if any.
+    pub path: Option<P>,
+    /// The number of expanded nodes.
+    pub expanded: usize,
+}
+
+impl<P> SearchOutcome<P> {
+    /// Returns true if a path was found.
+    pub fn is_reachable(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
+impl<S, C: Copy> SearchOutcome<StatePath<S, C>> {
+    /// Returns the found path's cost, or None if the goal was unreachable.
+    pub fn cost(&self) -> Option<C> {
+        self.path.as_ref().map(|path| path.cost)
+    }
+}
+
+impl<S: Eq + Hash, C: Copy> SearchOutcome<AllPaths<S, C>> {
+    /// Returns the cost of the cheapest paths, or None if the goal was unreachable.
+    pub fn cost(&self) -> Option<C> {
+        self.path.as_ref().map(|paths| paths.cost)
+    }
+}
+
+/// Find the cheapest path from `start` to a state accepted by `is_goal`.
+///
+/// `successors` returns the states reachable from a state,
+/// together with the cost of getting there.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// // Reach 10 from 1, by either adding one (cost 1) or doubling (cost 2).
+/// let outcome = dijkstra(
+///     1u32,
+///     |&n| [(n + 1, 1), (n * 2, 2)],
+///     |&n| n == 10,
+/// );
+/// let path = outcome.path.expect("A path");
+/// assert_eq!(path.cost, 6);
+/// assert_eq!(path.states, vec![1, 2, 4, 5, 10]);
+/// ```
+pub fn dijkstra<S, C, FS, I, FG>(
+    start: S,
+    successors: FS,
+    is_goal: FG,
+) -> SearchOutcome<StatePath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num + Ord + Copy,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(start, successors, is_goal, |_| Zero::zero())
+}
+
+/// Find the cheapest path from `start` to a state accepted by `is_goal` with A*.
+///
+/// Works like `dijkstra`, but `heuristic` estimates the remaining cost from a state to the goal.
+/// It must never overestimate the cost, or the found path might not be the cheapest.
+///
+/// Among states with the same estimate, the ones with the highest cost so far
+/// are expanded first, as they are expected to be closer to the goal.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let goal = Point { x: 5, y: 3 };
+/// let outcome = astar(
+///     Point { x: 0, y: 0 },
+///     |&pos: &Point| CARDINALS.map(|dir| (pos.walk(dir), 1)),
+///     |&pos| pos == goal,
+///     |pos| pos.manhattan_distance(goal),
+/// );
+/// assert_eq!(outcome.cost(), Some(8));
+/// assert_eq!(outcome.expanded, 8);
+/// ```
+pub fn astar<S, C, FS, I, FG, H>(
+    start: S,
+    successors: FS,
+    is_goal: FG,
+    heuristic: H,
+) -> SearchOutcome<StatePath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num + Ord + Copy,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+    H: FnMut(&S) -> C,
+{
+    bounded_astar(start, successors, is_goal, heuristic, None)
+}
+
+/// Find the cheapest path from `start` to a state accepted by `is_goal`
+/// that costs at most `max_cost`.
+///
+/// Works like `dijkstra`, but states that cost more than `max_cost` to reach are never searched,
+/// so the search also ends when there are infinitely many states and no goal can be reached.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// // Counting upwards never reaches 0.
+/// let outcome = dijkstra_within(1u32, |&n| [(n + 1, 1)], |&n| n == 0, 10);
+/// assert!(!outcome.is_reachable());
+/// assert_eq!(outcome.expanded, 11);
+///
+/// let outcome = dijkstra_within(1u32, |&n| [(n + 1, 1)], |&n| n == 11, 10);
+/// assert_eq!(outcome.cost(), Some(10));
+/// ```
+pub fn dijkstra_within<S, C, FS, I, FG>(
+    start: S,
+    successors: FS,
+    is_goal: FG,
+    max_cost: C,
+) -> SearchOutcome<StatePath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num + Ord + Copy,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    bounded_astar(start, successors, is_goal, |_| Zero::zero(), Some(max_cost))
+}
+
+/// The search behind `astar`, `dijkstra` and `dijkstra_within`.
+///
+/// States that cost more than `max_cost` to reach, if any, are never added.
+fn bounded_astar<S, C, FS, I, FG, H>(
+    start: S,
+    mut successors: FS,
+    mut is_goal: FG,
+    mut heuristic: H,
+    max_cost: Option<C>,
+) -> SearchOutcome<StatePath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num + Ord + Copy,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+    H: FnMut(&S) -> C,
+{
+    let mut best = HashMap::new();
+    let mut came_from = HashMap::new();
+    let mut to_expand = BinaryHeap::new();
+    let mut expanded = 0;
+    best.insert(start.clone(), Zero::zero());
+    to_expand.push(StateAndCost {
+        cost: (heuristic(&start), Reverse(C::zero())),
+        state: start,
+    });
+    while let Some(StateAndCost {
+        cost: (_, Reverse(cost)),
+        state,
+    }) = to_expand.pop()
+    {
+        if is_goal(&state) {
+            return SearchOutcome {
+                path: Some(reconstruct_path(&came_from, state, cost)),
+                expanded,
+            };
+        }
+        if best.get(&state).is_some_and(|&old_cost| old_cost < cost) {
+            continue;
+        }
+        expanded += 1;
+        for (next, step) in successors(&state) {
+            let new_cost = cost + step;
+            if max_cost.is_some_and(|max_cost| new_cost > max_cost) {
+                continue;
+            }
+            if best.get(&next).is_none_or(|&old_cost| new_cost < old_cost) {
+                best.insert(next.clone(), new_cost);
+                came_from.insert(next.clone(), state.clone());
+                to_expand.push(StateAndCost {
+                    cost: (new_cost + heuristic(&next), Reverse(new_cost)),
+                    state: next,
+                });
+            }
+        }
+    }
+    SearchOutcome {
+        path: None,
+        expanded,
+    }
+}
+
+/// Find all the cheapest paths from `start` to the states accepted by `is_goal`.
+///
+/// Works like `dijkstra`, but keeps all the ways each state can be reached at its lowest cost.
+/// The costs must be positive for the paths to be countable.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// // Walk from (0, 0) to (2, 2) via the cardinal directions.
+/// let goal = Point { x: 2, y: 2 };
+/// let outcome = dijkstra_all(
+///     Point { x: 0, y: 0 },
+///     |&pos: &Point| [Dir::East, Dir::South].map(|dir| (pos.walk(dir), 1)),
+///     |&pos| pos == goal,
+/// );
+/// let paths = outcome.path.expect("Paths");
+/// assert_eq!(paths.cost, 4);
+/// assert_eq!(paths.count(), num::BigUint::from(6u32));
+/// assert_eq!(paths.states().len(), 9);
+/// ```
+pub fn dijkstra_all<S, C, FS, I, FG>(
+    start: S,
+    mut successors: FS,
+    mut is_goal: FG,
+) -> SearchOutcome<AllPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Num + Ord + Copy,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut best = HashMap::new();
+    let mut parents: HashMap<S, Vec<S>> = HashMap::new();
+    let mut to_expand = BinaryHeap::new();
+    let mut expanded = 0;
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+    best.insert(start.clone(), Zero::zero());
+    to_expand.push(StateAndCost {
+        cost: C::zero(),
+        state: start,
+    });
+    while let Some(StateAndCost { cost, state }) = to_expand.pop() {
+        if best.get(&state).is_some_and(|&old_cost| old_cost < cost) {
+            continue;
+        }
+        if goal_cost.is_some_and(|goal_cost| goal_cost < cost) {
+            break;
+        }
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            goals.push(state);
+            continue;
+        }
+        expanded += 1;
+        for (next, step) in successors(&state) {
+            let new_cost = cost + step;
+            match best.get(&next) {
+                Some(&old_cost) if old_cost < new_cost => (),
+                Some(&old_cost) if old_cost == new_cost => {
+                    let list = parents.entry(next).or_default();
+                    if !list.contains(&state) {
+                        list.push(state.clone());
+                    }
+                }
+                _ => {
+                    best.insert(next.clone(), new_cost);
+                    parents.insert(next.clone(), vec![state.clone()]);
+                    to_expand.push(StateAndCost {
+                        cost: new_cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+    SearchOutcome {
+        path: goal_cost.map(|cost| AllPaths {
+            cost,
+            goals,
+            parents,
+        }),
+        expanded,
+    }
+}
+
+fn reconstruct_path<S, C>(came_from: &HashMap<S, S>, goal: S, cost: C) -> StatePath<S, C>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut states = vec![goal];
+    while let Some(prev) = came_from.get(states.last().expect("At least one state")) {
+        states.push(prev.clone());
+    }
+    states.reverse();
+    StatePath { cost, states }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_paths_beyond_u64() {
+        // Lattice paths through a 40x40 grid, there are (78 choose 39) of them.
+        let outcome = dijkstra_all(
+            (0u32, 0u32),
+            |&(x, y)| [(x + 1, y), (x, y + 1)].map(|pos| (pos, 1u32)),
+            |&pos| pos == (39, 39),
+        );
+        let mut expected = BigUint::one();
+        for i in 0..39u32 {
+            expected = expected * (78 - i) / (i + 1);
+        }
+        assert!(expected > BigUint::from(u64::MAX));
+        assert_eq!(outcome.path.expect("Paths").count(), expected);
+    }
+}
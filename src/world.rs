@@ -23,11 +23,35 @@ impl LengthType for i32 {}
 impl LengthType for i64 {}
 impl LengthType for i128 {}
 
+mod ansi;
+mod automaton;
+mod debug;
 mod dir;
+mod fill;
+mod grid;
+mod image;
+mod orientation;
+mod parse;
 mod point;
+mod region;
+mod render;
+mod search;
+mod sparse;
+mod sub_map;
+mod tilt;
+mod wrapping;
+pub use ansi::{Animation, Color, Palette, Style};
+pub use automaton::Automaton;
 pub use dir::*;
+pub use fill::Connectivity;
+pub use grid::{Grid, GridIterator, GridNeighborIterator};
 use num::*;
+pub use parse::{MapParseError, MapParseOptions};
 pub use point::Point;
+pub use region::{RegionStats, Regions};
+pub use search::{MapPath, TurnRules};
+pub use sparse::{SparseMap, SparseNeighborIterator};
+pub use wrapping::{WrappingMap, WrappingNeighborIterator};
 
 /// A struct to keep a Point together with a number.
 ///
@@ -37,6 +61,8 @@ pub use point::Point;
 /// `Ord` and `PartialOrd` are implemented for it. Only the cost field is compared,
 /// and the lowest cost is the greatest.
 ///
+/// See `StateAndCost` for searches over other states than Points.
+///
 /// # Example:
 /// ```
 /// # use advent_of_tools::*;
@@ -63,7 +89,7 @@ impl<T: LengthType + PartialOrd + Eq + PartialEq, U: Num + Ord + PartialOrd + Eq
     PartialOrd for PointAndCost<T, U>
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.cost.cmp(&self.cost))
+        Some(self.cmp(other))
     }
 }
 
@@ -80,7 +106,7 @@ impl<T: LengthType + PartialOrd + Eq + PartialEq, U: Num + Ord + PartialOrd + Eq
 /// assert_eq!(map.get_height(), 3);
 /// assert_eq!(map.find(b'@').len(), 1);
 /// ```
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct Map<T: LengthType = Length>
 where
     usize: TryFrom<T>,
@@ -338,42 +364,28 @@ where
 
     /// Create a Map from a string.
     ///
-    /// The string is assumed to end each line with a single linefeed.
     /// Each line becomes a row in the map.
     /// All lines are assumed to be of equal width.
+    ///
+    /// Panics if the map can't be parsed, see `try_from_str`.
     pub fn from_string(s: &str) -> Self
     where
         T: TryFrom<usize>,
         <T as TryFrom<usize>>::Error: std::fmt::Debug,
     {
-        let height = s.lines().count();
-        let width = s.lines().next().expect("At least one line").len();
-        let mut data = Vec::with_capacity(height * width);
-        for c in s.chars() {
-            if c != '\n' {
-                data.push(u8::try_from(c).expect("Ascii char"));
-            }
-        }
-
-        let width = T::try_from(width).expect("Positive width");
-        let height = T::try_from(height).expect("Positive height");
-        Self {
-            data,
-            width,
-            height,
-            has_border: false,
-        }
+        Self::try_from_str(s).unwrap_or_else(|err| panic!("Invalid map: {err}"))
     }
 
     /// Create a Map from a string with a border.
     ///
-    /// The string is assumed to end each line with a single linefeed.
     /// Each line becomes a row in the map.
     /// All lines are assumed to be of equal width.
     ///
     /// The map gets a border around it with b'+' in the corners,
     /// b'-' along the top and bottom edges and b'|' along the sides.
     ///
+    /// Panics if the map can't be parsed, see `try_from_str_with_border`.
+    ///
     /// # Example
     /// ```
     /// # use advent_of_tools::*;
@@ -391,38 +403,7 @@ where
         T: TryFrom<usize>,
         <T as TryFrom<usize>>::Error: std::fmt::Debug,
     {
-        let height = s.lines().count() + 2;
-        let width = s.lines().next().expect("At least one line").len() + 2;
-        let mut data = Vec::with_capacity(height * width);
-        data.push(b'+');
-        for _x in 0..width - 2 {
-            data.push(b'-');
-        }
-        data.push(b'+');
-        data.push(b'|');
-        for c in s.chars() {
-            if c != '\n' {
-                data.push(u8::try_from(c).expect("Ascii char"));
-            } else {
-                data.push(b'|');
-                data.push(b'|');
-            }
-        }
-        data.push(b'|');
-        data.push(b'+');
-        for _x in 0..width - 2 {
-            data.push(b'-');
-        }
-        data.push(b'+');
-
-        let width = T::try_from(width).expect("Positive width");
-        let height = T::try_from(height).expect("Positive height");
-        Self {
-            data,
-            width,
-            height,
-            has_border: true,
-        }
+        Self::try_from_str_with_border(s).unwrap_or_else(|err| panic!("Invalid map: {err}"))
     }
 
     /// Print the map to stdout with an overlay provided by f.
@@ -445,19 +426,12 @@ where
     ///   }
     /// })
     /// ```
-    pub fn print_with_overlay<F>(&self, mut f: F)
+    pub fn print_with_overlay<F>(&self, f: F)
     where
         F: FnMut(Point<T>, u8) -> u8,
     {
-        for y in range(Zero::zero(), self.height) {
-            for x in range(Zero::zero(), self.width) {
-                let pos = Point { x, y };
-                let mut c = self.get_at_unchecked(pos);
-                c = f(pos, c);
-                print!("{}", char::from(c));
-            }
-            println!();
-        }
+        self.write_with_overlay(&mut std::io::stdout().lock(), true, f)
+            .expect("Writing to stdout");
     }
 
     /// Print the map to stdout.
@@ -484,7 +458,7 @@ where
     ///
     /// assert_eq!(map.iter().filter(|&(_pos, tile)| tile == b'a').count(), 1);
     /// ```
-    pub fn iter(&self) -> MapIterator<T> {
+    pub fn iter(&self) -> MapIterator<'_, T> {
         MapIterator::new(self)
     }
 
@@ -512,7 +486,7 @@ where
     /// Point { x: 0, y: 1}, South, d
     /// Point { x: 1, y: 1}, SouthEast, e
     /// ```
-    pub fn neighbors(&self, pos: Point<T>) -> MapNeighborIterator<T> {
+    pub fn neighbors(&self, pos: Point<T>) -> MapNeighborIterator<'_, T> {
         MapNeighborIterator::new(self, pos)
     }
 
@@ -650,6 +624,7 @@ where
     /// flood fill the map from point `pos` with `tile`.
     ///
     /// Only fills via the cardinal directions from each position.
+    /// Returns the number of filled positions.
     ///
     /// # Example:
     /// ```
@@ -661,45 +636,53 @@ where
     /// // #.###
     /// let mut map = Map::<i32>::from_string("###.#\n#.#..\n#.###\n");
     ///
-    /// map.flood_cardinal(Point{x: 0, y: 2}, b'#', b'!');
+    /// assert_eq!(map.flood_cardinal(Point{x: 0, y: 2}, b'#', b'!'), 9);
     ///
     /// assert_eq!(map.get_at_unchecked(Point{x: 4, y: 2}), b'!');
     /// assert_eq!(map.get_at_unchecked(Point{x: 4, y: 0}), b'#');
     /// ```
-    pub fn flood_cardinal(&mut self, pos: Point<T>, empty: u8, tile: u8) {
-        if self.get_at_unchecked(pos) != empty {
-            // Nothing to fill here
-            return;
+    pub fn flood_cardinal(&mut self, pos: Point<T>, empty: u8, tile: u8) -> usize {
+        if empty == tile {
+            return 0;
         }
-        let min_pos = self.walk_until(pos, Dir::West, |_, c| c != empty);
-        let max_pos = self.walk_until(pos, Dir::East, |_, c| c != empty);
+        self.flood_cardinal_with(pos, &mut |_, c| c == empty, &mut |_, _| tile)
+    }
 
-        let mut pos = min_pos;
-        while pos.x <= max_pos.x {
-            self.set_at(pos, tile);
-            pos = pos.walk(Dir::East);
-        }
-        pos = min_pos;
-        while pos.x <= max_pos.x {
-            pos.y -= One::one();
-            if pos.y >= Zero::zero() {
-                self.flood_cardinal(pos, empty, tile);
-            }
-            pos.y = pos.y + One::one() + One::one();
-            if pos.y < self.get_height() {
-                self.flood_cardinal(pos, empty, tile);
-            }
-            pos.y -= One::one();
-            pos = pos.walk(Dir::East);
+    /// flood fill the map from point `pos` with `tile`
+    /// and return the filled positions.
+    ///
+    /// Only fills via the cardinal directions from each position.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = Map::<i32>::from_string("#..\n.#.\n");
+    ///
+    /// let mut filled = map.flood_cardinal_points(Point{x: 1, y: 0}, b'.', b'o');
+    /// filled.sort();
+    ///
+    /// assert_eq!(filled, vec![Point{x: 1, y: 0}, Point{x: 2, y: 0}, Point{x: 2, y: 1}]);
+    /// assert_eq!(map.get_at_unchecked(Point{x: 0, y: 1}), b'.');
+    /// ```
+    pub fn flood_cardinal_points(&mut self, pos: Point<T>, empty: u8, tile: u8) -> Vec<Point<T>> {
+        let mut filled = Vec::new();
+        if empty != tile {
+            self.flood_cardinal_with(pos, &mut |_, c| c == empty, &mut |pos, _| {
+                filled.push(pos);
+                tile
+            });
         }
+        filled
     }
 
     /// flood fill the map from point `pos`.
     ///
     /// `is_ok_f` says if it is ok to fill the position,
     /// `tile_f` says what it should be filled with.
+    /// `is_ok_f` must not be ok with the tiles that `tile_f` returns.
     ///
     /// Only fills via the cardinal directions from each position.
+    /// Returns the number of filled positions.
     ///
     /// # Example:
     /// ```
@@ -716,37 +699,37 @@ where
     /// assert_eq!(map.get_at_unchecked(Point{x: 4, y: 2}), b'!');
     /// assert_eq!(map.get_at_unchecked(Point{x: 4, y: 0}), b'#');
     /// ```
-    pub fn flood_cardinal_with<O, F>(&mut self, pos: Point<T>, is_ok_f: &mut O, tile_f: &mut F)
+    pub fn flood_cardinal_with<O, F>(
+        &mut self,
+        pos: Point<T>,
+        is_ok_f: &mut O,
+        tile_f: &mut F,
+    ) -> usize
     where
         O: FnMut(Point<T>, u8) -> bool,
         F: FnMut(Point<T>, u8) -> u8,
     {
-        if !is_ok_f(pos, self.get_at_unchecked(pos)) {
-            // Nothing to fill here
-            return;
-        }
-        let min_pos = self.walk_until(pos, Dir::West, |pos, c| !is_ok_f(pos, c));
-        let max_pos = self.walk_until(pos, Dir::East, |pos, c| !is_ok_f(pos, c));
-
-        let mut pos = min_pos;
-        while pos.x <= max_pos.x {
-            let val = tile_f(pos, self.get_at_unchecked(pos));
-            self.set_at(pos, val);
-            pos = pos.walk(Dir::East);
-        }
-        pos = min_pos;
-        while pos.x <= max_pos.x {
-            pos.y -= One::one();
-            if pos.y >= Zero::zero() {
-                self.flood_cardinal_with(pos, is_ok_f, tile_f);
-            }
-            pos.y = pos.y + One::one() + One::one();
-            if pos.y < self.get_height() {
-                self.flood_cardinal_with(pos, is_ok_f, tile_f);
-            }
-            pos.y -= One::one();
-            pos = pos.walk(Dir::East);
-        }
+        let area = (
+            Point {
+                x: Zero::zero(),
+                y: Zero::zero(),
+            },
+            Point {
+                x: self.width,
+                y: self.height,
+            },
+        );
+        fill::scanline_fill(
+            self,
+            pos,
+            area,
+            false,
+            |map, pos| is_ok_f(pos, map.get_at_unchecked(pos)),
+            |map, pos| {
+                let val = tile_f(pos, map.get_at_unchecked(pos));
+                map.set_at(pos, val);
+            },
+        )
     }
 
     /// Finds all tiles matching `needle`.
@@ -756,42 +739,20 @@ where
             .collect()
     }
 
+    /// Find the cost of the cheapest path from `from` to `to`.
+    ///
+    /// `f` is called with the map, the neighbor's position, the direction to it and its tile.
+    /// It returns the cost of stepping there, or None if it can't be entered.
+    ///
+    /// If `to` can't be reached, zero is returned.
+    /// Use `search` to tell an unreachable goal apart from a zero cost,
+    /// or `bfs_path` to also get the path.
     pub fn bfs<F, U>(&self, from: Point<T>, to: Point<T>, f: &mut F) -> U
     where
         F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
         U: Num + Ord + Copy + std::fmt::Debug,
     {
-        // TODO: Give path instead?
-        let mut expanded = std::collections::HashMap::new();
-        let mut to_expand = std::collections::BinaryHeap::new();
-        to_expand.push(PointAndCost {
-            cost: Zero::zero(),
-            point: from,
-        });
-        while let Some(PointAndCost { cost, point: pos }) = to_expand.pop() {
-            if to == pos {
-                return cost;
-            }
-            if let Some(old_cost) = expanded.get_mut(&pos) {
-                if *old_cost <= cost {
-                    continue;
-                } else {
-                    *old_cost = cost;
-                }
-            } else {
-                expanded.insert(pos, cost);
-            }
-            for (new_cost, pos) in self
-                .neighbors(pos)
-                .filter_map(|(pos, dir, c)| f(self, pos, dir, c).map(|step| (step + cost, pos)))
-            {
-                to_expand.push(PointAndCost {
-                    cost: new_cost,
-                    point: pos,
-                });
-            }
-        }
-        Zero::zero()
+        self.search(from, to, f).cost().unwrap_or_else(Zero::zero)
     }
 }
 
@@ -819,4 +780,26 @@ mod test {
         }
         assert_eq!(count, 6);
     }
+
+    #[test]
+    fn test_add_border_matches_grid() {
+        let mut map = super::Map::<i32>::new(3, 3);
+        let mut grid = super::Grid::<u8, i32>::from_map(&map, |tile| tile);
+        map.add_border(b'#');
+        grid.add_border(b'#');
+        assert!(!grid.has_border());
+        map.transform(|_, _, _| b'o');
+        grid.transform(|_, _, _| b'o');
+        assert_eq!(map.find(b'o').len(), 9);
+        assert_eq!(grid.find(&b'o'), map.find(b'o'));
+    }
+
+    #[test]
+    fn test_flood_large_map() {
+        let mut map = super::Map::<i32>::new(1000, 1000);
+        map.set_at(super::Point { x: 500, y: 0 }, b'#');
+        let filled = map.flood_cardinal(super::Point { x: 999, y: 999 }, b'.', b'#');
+        assert_eq!(filled, 1000 * 1000 - 1);
+        assert_eq!(map.find(b'.'), vec![]);
+    }
 }
@@ -0,0 +1,311 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// A terminal color.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[allow(missing_docs)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// A 24-bit color, not supported by all terminals.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Get the ANSI parameters for the color, `base` is 30 for foreground
+    /// and 40 for background colors.
+    fn params(self, base: u8) -> String {
+        use Color::*;
+        let offset = match self {
+            Black => 0,
+            Red => 1,
+            Green => 2,
+            Yellow => 3,
+            Blue => 4,
+            Magenta => 5,
+            Cyan => 6,
+            White => 7,
+            Rgb(r, g, b) => return format!("{};2;{r};{g};{b}", base + 8),
+        };
+        (base + offset).to_string()
+    }
+}
+
+/// How a tile is drawn in a terminal.
+///
+/// The default style is the terminal's default.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Style {
+    /// The color of the tile's character.
+    pub fg: Option<Color>,
+    /// The color behind the tile's character.
+    pub bg: Option<Color>,
+    /// If the tile's character is drawn in bold.
+    pub bold: bool,
+}
+
+impl Style {
+    /// Create a style with a foreground color.
+    pub fn fg(color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            ..Default::default()
+        }
+    }
+
+    /// Create a style with a background color.
+    pub fn bg(color: Color) -> Self {
+        Self {
+            bg: Some(color),
+            ..Default::default()
+        }
+    }
+
+    /// Get the ANSI escape sequence that switches to the style.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let style = Style { fg: Some(Color::Red), bg: Some(Color::Rgb(1, 2, 3)), bold: true };
+    ///
+    /// assert_eq!(style.escape(), "\x1b[0;1;31;48;2;1;2;3m");
+    /// assert_eq!(Style::default().escape(), "\x1b[0m");
+    /// ```
+    pub fn escape(&self) -> String {
+        let mut params = vec!["0".to_string()];
+        if self.bold {
+            params.push("1".to_string());
+        }
+        if let Some(fg) = self.fg {
+            params.push(fg.params(30));
+        }
+        if let Some(bg) = self.bg {
+            params.push(bg.params(40));
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+/// A table of the styles to draw tiles with.
+///
+/// Tiles not in the table are drawn with the default style.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let palette: Palette = [(b'#', Style::fg(Color::Blue)), (b'O', Style::bg(Color::Red))]
+///     .into_iter()
+///     .collect();
+///
+/// assert_eq!(palette.style(b'#'), Style::fg(Color::Blue));
+/// assert_eq!(palette.style(b'.'), Style::default());
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Palette {
+    styles: HashMap<u8, Style>,
+}
+
+impl Palette {
+    /// Create an empty palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style of a tile.
+    pub fn set(&mut self, tile: u8, style: Style) {
+        self.styles.insert(tile, style);
+    }
+
+    /// Get the style of a tile.
+    pub fn style(&self, tile: u8) -> Style {
+        self.styles.get(&tile).copied().unwrap_or_default()
+    }
+}
+
+impl FromIterator<(u8, Style)> for Palette {
+    fn from_iter<I: IntoIterator<Item = (u8, Style)>>(iter: I) -> Self {
+        Self {
+            styles: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Write the map to `out` with ANSI colors and an overlay provided by f.
+    ///
+    /// Works like `write_with_overlay`, except `f` also returns the style
+    /// of the tile. Escape sequences are only written when the style changes
+    /// and every line ends with the default style.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("#..\n");
+    /// let mut out = Vec::new();
+    ///
+    /// map.write_ansi_with_overlay(&mut out, true, |_pos, tile| {
+    ///     let style = if tile == b'#' { Style::fg(Color::Red) } else { Style::default() };
+    ///     (tile, style)
+    /// })
+    /// .expect("Writing to a Vec");
+    /// assert_eq!(String::from_utf8(out).unwrap(), "\x1b[0;31m#\x1b[0m..\n");
+    /// ```
+    pub fn write_ansi_with_overlay<W, F>(
+        &self,
+        out: &mut W,
+        with_border: bool,
+        f: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(Point<T>, u8) -> (u8, Style),
+    {
+        self.write_lines(with_border, f, |line| out.write_all(line.as_bytes()))
+    }
+
+    /// Print the map to stdout with the tiles' colors from `palette`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string_with_border("#..\n.#.\n");
+    /// let palette: Palette = [(b'#', Style::fg(Color::Yellow))].into_iter().collect();
+    ///
+    /// map.print_ansi(&palette);
+    /// ```
+    pub fn print_ansi(&self, palette: &Palette) {
+        self.print_ansi_highlighted(palette, &[], Style::default());
+    }
+
+    /// Print the map to stdout with the tiles' colors from `palette`,
+    /// except that the positions in `highlight` are drawn with `style`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("...\n.#.\n...\n");
+    /// let path = map.bfs_path(Point { x: 0, y: 0 }, Point { x: 2, y: 2 }, &mut |_, _, _, tile| {
+    ///     (tile == b'.').then_some(1)
+    /// }).expect("A path");
+    ///
+    /// map.print_ansi_highlighted(&Palette::new(), &path.points, Style::bg(Color::Green));
+    /// ```
+    pub fn print_ansi_highlighted(&self, palette: &Palette, highlight: &[Point<T>], style: Style) {
+        let highlight: HashSet<Point<T>> = highlight.iter().copied().collect();
+        self.write_ansi_with_overlay(&mut io::stdout().lock(), true, |pos, tile| {
+            if highlight.contains(&pos) {
+                (tile, style)
+            } else {
+                (tile, palette.style(tile))
+            }
+        })
+        .expect("Writing to stdout");
+    }
+}
+
+/// Redraws maps in place in a terminal, one frame at a time.
+///
+/// The first frame clears the screen, later frames move the cursor back to the
+/// top left corner and draw over the previous frame.
+/// Frames are drawn at most once every `frame_delay`.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// # use std::time::Duration;
+/// let mut map = Map::<i32>::from_string("o..\n");
+/// let mut animation = Animation::with_writer(Vec::new(), Duration::ZERO);
+///
+/// for x in 1..3 {
+///     animation.draw(&map, |_pos, tile| (tile, Style::default())).expect("Writing to a Vec");
+///     map.set_at(Point { x: x - 1, y: 0 }, b'.');
+///     map.set_at(Point { x, y: 0 }, b'o');
+/// }
+/// assert_eq!(animation.frames(), 2);
+/// assert_eq!(
+///     String::from_utf8(animation.into_writer()).unwrap(),
+///     "\x1b[2J\x1b[Ho..\n\x1b[H.o.\n"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Animation<W: Write> {
+    out: W,
+    frame_delay: Duration,
+    last_frame: Option<Instant>,
+    frames: usize,
+}
+
+impl Animation<io::Stdout> {
+    /// Create an animation on stdout, drawing `fps` frames per second.
+    pub fn new(fps: u32) -> Self {
+        Self::with_writer(io::stdout(), Duration::from_secs(1) / fps.max(1))
+    }
+}
+
+impl<W: Write> Animation<W> {
+    /// Create an animation that writes to `out`,
+    /// waiting at least `frame_delay` between the frames.
+    pub fn with_writer(out: W, frame_delay: Duration) -> Self {
+        Self {
+            out,
+            frame_delay,
+            last_frame: None,
+            frames: 0,
+        }
+    }
+
+    /// Get the number of drawn frames.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the writer.
+    pub fn into_writer(self) -> W {
+        self.out
+    }
+
+    /// Draw the next frame, waiting until the frame delay has passed.
+    ///
+    /// `f` works like for `Map::write_ansi_with_overlay`.
+    pub fn draw<T, F>(&mut self, map: &Map<T>, f: F) -> io::Result<()>
+    where
+        T: LengthType,
+        usize: TryFrom<T>,
+        <usize as TryFrom<T>>::Error: std::fmt::Debug,
+        F: FnMut(Point<T>, u8) -> (u8, Style),
+    {
+        match self.last_frame {
+            Some(last_frame) => {
+                let elapsed = last_frame.elapsed();
+                if elapsed < self.frame_delay {
+                    std::thread::sleep(self.frame_delay - elapsed);
+                }
+            }
+            None => self.out.write_all(b"\x1b[2J")?,
+        }
+        self.out.write_all(b"\x1b[H")?;
+        map.write_ansi_with_overlay(&mut self.out, true, f)?;
+        self.out.flush()?;
+        self.last_frame = Some(Instant::now());
+        self.frames += 1;
+        Ok(())
+    }
+}
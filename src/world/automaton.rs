@@ -0,0 +1,247 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use num::*;
+
+/// Steps a cellular automaton over a Map, generation by generation.
+///
+/// `rule` gets called with the current generation's map, the position and
+/// the tile and returns the tile for the next generation, like for `Map::transform`.
+/// The border, if any, is never changed.
+///
+/// The back buffer is kept between generations, so stepping doesn't allocate new maps.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// // A blinker in Conway's Game of Life.
+/// let map = Map::<i32>::from_string(".....\n..#..\n..#..\n..#..\n.....\n");
+/// let mut life = Automaton::new(map.clone(), |map, pos, tile| {
+///     match (tile, map.count_neighbors(pos, b'#')) {
+///         (b'#', 2 | 3) | (b'.', 3) => b'#',
+///         _ => b'.',
+///     }
+/// });
+///
+/// assert_eq!(life.step(), 4);
+/// assert_eq!(life.map(), &Map::from_string(".....\n.....\n.###.\n.....\n.....\n"));
+///
+/// life.run(9);
+/// assert_eq!(life.generation(), 10);
+/// assert_eq!(life.map(), &map);
+/// ```
+pub struct Automaton<T: LengthType, F>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    map: Map<T>,
+    back: Map<T>,
+    rule: F,
+    generation: usize,
+    track_changes: bool,
+    /// The positions changed by the last generation.
+    changed: Vec<Point<T>>,
+    /// Marks positions already queued for evaluation while tracking changes.
+    queued: Vec<bool>,
+}
+
+impl<T: LengthType, F> Automaton<T, F>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    F: FnMut(&Map<T>, Point<T>, u8) -> u8,
+{
+    /// Create an automaton that evaluates every tile in every generation.
+    pub fn new(map: Map<T>, rule: F) -> Self {
+        Self {
+            back: map.clone(),
+            map,
+            rule,
+            generation: 0,
+            track_changes: false,
+            changed: Vec::new(),
+            queued: Vec::new(),
+        }
+    }
+
+    /// Create an automaton that, after the first generation, only evaluates
+    /// the tiles that changed in the previous generation and their 8 neighbors.
+    ///
+    /// This is only correct if `rule` just looks at the tile and its 8 neighbors.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// // Sand falls one step down per generation.
+    /// let map = Map::<i32>::from_string_with_border("o..\n...\n...\n");
+    /// let mut sand = Automaton::with_change_tracking(map, |map, pos, tile| {
+    ///     let above = map.get_at_unchecked(pos.walk(Dir::North));
+    ///     let below = map.get_at_unchecked(pos.walk(Dir::South));
+    ///     match tile {
+    ///         b'o' if below == b'.' => b'.',
+    ///         b'.' if above == b'o' => b'o',
+    ///         _ => tile,
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(sand.run_until_stable(), 2);
+    /// assert_eq!(sand.into_map(), Map::from_string_with_border("...\n...\no..\n"));
+    /// ```
+    pub fn with_change_tracking(map: Map<T>, rule: F) -> Self {
+        let queued = vec![false; map.data.len()];
+        Self {
+            track_changes: true,
+            queued,
+            ..Self::new(map, rule)
+        }
+    }
+
+    /// Get the current generation's map.
+    pub fn map(&self) -> &Map<T> {
+        &self.map
+    }
+
+    /// Returns the current generation's map.
+    pub fn into_map(self) -> Map<T> {
+        self.map
+    }
+
+    /// Get the number of generations run so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Get the positions that were changed by the last generation.
+    pub fn changed(&self) -> &[Point<T>] {
+        &self.changed
+    }
+
+    /// Run one generation.
+    ///
+    /// Returns the number of changed tiles.
+    pub fn step(&mut self) -> usize {
+        let (from, to) = self.map.inner_area();
+        let inside =
+            |pos: Point<T>| pos.x >= from.x && pos.y >= from.y && pos.x < to.x && pos.y < to.y;
+
+        let mut changed = Vec::new();
+        if self.track_changes && self.generation > 0 {
+            let mut candidates = Vec::new();
+            for &pos in &self.changed {
+                let neighbors = self.map.neighbors(pos).map(|(pos, _, _)| pos);
+                for pos in std::iter::once(pos).chain(neighbors) {
+                    let idx = self.map.get_index_for(pos);
+                    if inside(pos) && !self.queued[idx] {
+                        self.queued[idx] = true;
+                        candidates.push(pos);
+                    }
+                }
+            }
+            for pos in candidates {
+                self.queued[self.map.get_index_for(pos)] = false;
+                self.evaluate(pos, &mut changed);
+            }
+        } else {
+            for y in range(from.y, to.y) {
+                for x in range(from.x, to.x) {
+                    self.evaluate(Point { x, y }, &mut changed);
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.map, &mut self.back);
+        // Bring the back buffer up to date with the new generation.
+        for &pos in &changed {
+            self.back.set_at(pos, self.map.get_at_unchecked(pos));
+        }
+        self.changed = changed;
+        self.generation += 1;
+        self.changed.len()
+    }
+
+    /// Evaluate the rule at `pos` and write any change to the back buffer.
+    fn evaluate(&mut self, pos: Point<T>, changed: &mut Vec<Point<T>>) {
+        let tile = self.map.get_at_unchecked(pos);
+        let new_tile = (self.rule)(&self.map, pos, tile);
+        if new_tile != tile {
+            self.back.set_at(pos, new_tile);
+            changed.push(pos);
+        }
+    }
+
+    /// Run `generations` generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Run generations until one doesn't change any tile.
+    ///
+    /// Returns the number of generations that changed tiles.
+    /// Never returns if the automaton doesn't stabilize.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut count = 0;
+        while self.step() > 0 {
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Count how many of the 8 neighbors of `pos` are `tile`.
+    ///
+    /// Neighbors outside of the map are not counted.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("#.#\n.##\n");
+    ///
+    /// assert_eq!(map.count_neighbors(Point { x: 1, y: 0 }, b'#'), 4);
+    /// assert_eq!(map.count_neighbors(Point { x: 0, y: 0 }, b'#'), 1);
+    /// ```
+    pub fn count_neighbors(&self, pos: Point<T>, tile: u8) -> usize {
+        self.neighbors(pos).filter(|&(_, _, t)| t == tile).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn life(map: &Map<i32>, pos: Point<i32>, tile: u8) -> u8 {
+        match (tile, map.count_neighbors(pos, b'#')) {
+            (b'#', 2 | 3) | (b'.', 3) => b'#',
+            _ => b'.',
+        }
+    }
+
+    #[test]
+    fn test_change_tracking_matches_full_evaluation() {
+        let map = Map::<i32>::from_string(
+            "..........\n..#.......\n...#......\n.###......\n..........\n..........\n......##..\n......##..\n..........\n..........\n",
+        );
+        let mut full = Automaton::new(map.clone(), life);
+        let mut tracked = Automaton::with_change_tracking(map, life);
+        for _ in 0..30 {
+            assert_eq!(full.step(), tracked.step());
+            assert_eq!(full.map(), tracked.map());
+        }
+
+        let block = Map::<i32>::from_string("....\n.##.\n.##.\n....\n");
+        let mut still = Automaton::with_change_tracking(block, life);
+        assert_eq!(still.run_until_stable(), 0);
+        assert_eq!(still.generation(), 1);
+    }
+}
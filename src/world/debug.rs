@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use num::*;
+use std::fmt;
+
+/// Asserts that two maps are equal.
+///
+/// On failure, the panic message shows the maps side by side,
+/// followed by a column where the differing tiles are marked with `*`.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let mut map = Map::<i32>::from_string("ab\ncd\n");
+/// map.set_at(Point { x: 1, y: 1 }, b'd');
+///
+/// assert_map_eq!(map, Map::from_string("ab\ncd\n"));
+/// assert_map_eq!(map, Map::from_string("ab\ncd\n"), "after setting {}", "d");
+/// ```
+#[macro_export]
+macro_rules! assert_map_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed\n{}",
+                        left.diff_side_by_side(right)
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        left.diff_side_by_side(right)
+                    );
+                }
+            }
+        }
+    };
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the tiles of row `y` as text, or an empty String outside of the map.
+    fn line(&self, y: T) -> String {
+        if y < Zero::zero() || y >= self.height {
+            return String::new();
+        }
+        range(Zero::zero(), self.width)
+            .map(|x| char::from(self.get_at_unchecked(Point { x, y })))
+            .collect()
+    }
+
+    /// Show this map and `other` side by side, followed by a column
+    /// where the tiles that differ between them are marked with `*`.
+    ///
+    /// Used by `assert_map_eq!`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let left = Map::<i32>::from_string("ab\ncd\n");
+    /// let right = Map::<i32>::from_string("ab\nxd\n");
+    ///
+    /// assert_eq!(
+    ///     left.diff_side_by_side(&right),
+    ///     "left 2x2 | right 2x2\nab | ab |\ncd | xd | *\n"
+    /// );
+    /// ```
+    pub fn diff_side_by_side(&self, other: &Self) -> String {
+        let describe = |map: &Self| {
+            let border = if map.has_border { " with border" } else { "" };
+            format!("{:?}x{:?}{border}", map.width, map.height)
+        };
+        let mut s = format!("left {} | right {}\n", describe(self), describe(other));
+
+        let width = self.width.max(other.width);
+        let height = self.height.max(other.height);
+        let left_width = usize::try_from(self.width).expect("Positive width");
+        let right_width = usize::try_from(other.width).expect("Positive width");
+        for y in range(Zero::zero(), height) {
+            let marks: String = range(Zero::zero(), width)
+                .map(|x| {
+                    let pos = Point { x, y };
+                    if self.get_at(pos) == other.get_at(pos) {
+                        ' '
+                    } else {
+                        '*'
+                    }
+                })
+                .collect();
+            let line = format!(
+                "{:left_width$} | {:right_width$} | {marks}",
+                self.line(y),
+                other.line(y),
+            );
+            s.push_str(line.trim_end());
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// Shows the size of the map and its tiles as lines of text.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let map = Map::<i32>::from_string("ab\ncd\n");
+///
+/// assert_eq!(
+///     format!("{map:?}"),
+///     r#"Map { width: 2, height: 2, has_border: false, lines: ["ab", "cd"] }"#
+/// );
+/// ```
+impl<T: LengthType> fmt::Debug for Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = range(Zero::zero(), self.height)
+            .map(|y| self.line(y))
+            .collect();
+        f.debug_struct("Map")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("has_border", &self.has_border)
+            .field("lines", &lines)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "left 3x2 | right 2x2\nabc | ab |   *\ndef | xe | * *\n")]
+    fn test_assert_map_eq_shows_diff() {
+        assert_map_eq!(
+            Map::<i32>::from_string("abc\ndef\n"),
+            Map::from_string("ab\nxe\n")
+        );
+    }
+}
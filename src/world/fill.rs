@@ -0,0 +1,242 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, LengthType, Map, Point, CARDINALS};
+use num::*;
+
+/// How positions connect to their neighbors.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Connectivity {
+    /// Only via the cardinal directions.
+    Cardinal,
+    /// Via all 8 directions.
+    Eight,
+}
+
+/// All 8 directions, clockwise from North.
+const EIGHT: [Dir; 8] = [
+    Dir::North,
+    Dir::NorthEast,
+    Dir::East,
+    Dir::SouthEast,
+    Dir::South,
+    Dir::SouthWest,
+    Dir::West,
+    Dir::NorthWest,
+];
+
+impl Connectivity {
+    /// Returns the directions positions connect via.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// assert_eq!(Connectivity::Cardinal.dirs(), &CARDINALS);
+    /// assert_eq!(Connectivity::Eight.dirs().len(), 8);
+    /// ```
+    pub fn dirs(self) -> &'static [Dir] {
+        match self {
+            Connectivity::Cardinal => &CARDINALS,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// Scanline fill from `start` within the area from `from` to `to`.
+///
+/// `can_fill` says if a position should be filled,
+/// it must return false for positions that `fill` has filled.
+/// With `diagonal`, the fill also spreads diagonally.
+///
+/// Uses an explicit stack instead of recursion, so it can fill large areas.
+/// Returns the number of filled positions.
+pub(super) fn scanline_fill<T, S, C, F>(
+    state: &mut S,
+    start: Point<T>,
+    (from, to): (Point<T>, Point<T>),
+    diagonal: bool,
+    mut can_fill: C,
+    mut fill: F,
+) -> usize
+where
+    T: LengthType,
+    C: FnMut(&S, Point<T>) -> bool,
+    F: FnMut(&mut S, Point<T>),
+{
+    let mut count = 0;
+    let mut to_fill = vec![start];
+    while let Some(pos) = to_fill.pop() {
+        if !can_fill(state, pos) {
+            continue;
+        }
+        let mut min_pos = pos;
+        while min_pos.x > from.x && can_fill(state, min_pos.walk(Dir::West)) {
+            min_pos = min_pos.walk(Dir::West);
+        }
+        let mut max_pos = pos;
+        while max_pos.x + One::one() < to.x && can_fill(state, max_pos.walk(Dir::East)) {
+            max_pos = max_pos.walk(Dir::East);
+        }
+        for x in range_inclusive(min_pos.x, max_pos.x) {
+            fill(state, Point { x, y: pos.y });
+            count += 1;
+        }
+
+        let (min_x, max_x) = if diagonal {
+            (
+                (min_pos.x - One::one()).max(from.x),
+                (max_pos.x + One::one()).min(to.x - One::one()),
+            )
+        } else {
+            (min_pos.x, max_pos.x)
+        };
+        for y in [pos.y - One::one(), pos.y + One::one()] {
+            if y < from.y || y >= to.y {
+                continue;
+            }
+            let mut in_run = false;
+            for x in range_inclusive(min_x, max_x) {
+                let next = Point { x, y };
+                let fillable = can_fill(state, next);
+                if fillable && !in_run {
+                    to_fill.push(next);
+                }
+                in_run = fillable;
+            }
+        }
+    }
+    count
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// flood fill the map from point `pos` with `tile`.
+    ///
+    /// Fills via all 8 directions from each position.
+    /// Returns the number of filled positions.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = Map::<i32>::from_string("#..\n.#.\n..#\n");
+    ///
+    /// assert_eq!(map.flood_eight(Point{x: 0, y: 0}, b'#', b'!'), 3);
+    /// assert_eq!(map.get_at_unchecked(Point{x: 2, y: 2}), b'!');
+    /// ```
+    pub fn flood_eight(&mut self, pos: Point<T>, empty: u8, tile: u8) -> usize {
+        if empty == tile {
+            return 0;
+        }
+        let area = (
+            Point {
+                x: Zero::zero(),
+                y: Zero::zero(),
+            },
+            Point {
+                x: self.width,
+                y: self.height,
+            },
+        );
+        scanline_fill(
+            self,
+            pos,
+            area,
+            true,
+            |map, pos| map.get_at_unchecked(pos) == empty,
+            |map, pos| map.set_at(pos, tile),
+        )
+    }
+
+    /// flood fill the map from point `pos`, spreading via `dirs`.
+    ///
+    /// Use `flood_cardinal_with` or `flood_eight` when spreading in the cardinal
+    /// or all 8 directions, they don't need to keep track of the visited positions.
+    ///
+    /// `is_ok_f` says if it is ok to fill the position,
+    /// `tile_f` says what it should be filled with.
+    /// Each position is filled at most once.
+    /// Returns the number of filled positions.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = Map::<i32>::from_string("..#\n...\n#..\n");
+    ///
+    /// // Only spread diagonally, like a bishop.
+    /// let dirs = [Dir::NorthEast, Dir::SouthEast, Dir::SouthWest, Dir::NorthWest];
+    /// let filled = map.flood_dirs_with(Point{x: 0, y: 0}, &dirs, &mut |_pos, t| t == b'.', &mut |_pos, _tile| b'o');
+    ///
+    /// assert_eq!(filled, 3);
+    /// assert_eq!(map, Map::from_string("o.#\n.o.\n#.o\n"));
+    /// ```
+    pub fn flood_dirs_with<O, F>(
+        &mut self,
+        pos: Point<T>,
+        dirs: &[Dir],
+        is_ok_f: &mut O,
+        tile_f: &mut F,
+    ) -> usize
+    where
+        O: FnMut(Point<T>, u8) -> bool,
+        F: FnMut(Point<T>, u8) -> u8,
+    {
+        if !self.is_inside_map(pos) || !is_ok_f(pos, self.get_at_unchecked(pos)) {
+            return 0;
+        }
+        let mut visited = vec![false; self.data.len()];
+        visited[self.get_index_for(pos)] = true;
+        let mut to_fill = vec![pos];
+        let mut count = 0;
+        while let Some(pos) = to_fill.pop() {
+            let val = tile_f(pos, self.get_at_unchecked(pos));
+            self.set_at(pos, val);
+            count += 1;
+            for &dir in dirs {
+                let next = pos.walk(dir);
+                if !self.is_inside_map(next) {
+                    continue;
+                }
+                let idx = self.get_index_for(next);
+                if !visited[idx] && is_ok_f(next, self.get_at_unchecked(next)) {
+                    visited[idx] = true;
+                    to_fill.push(next);
+                }
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flood_outside_of_diagonal_loop() {
+        // The diamond only has diagonal gaps, which only an 8-connected fill gets through.
+        let mut map = Map::<i32>::from_string("..#..\n.#.#.\n#...#\n.#.#.\n..#..\n");
+        let mut cardinal = map.clone();
+        let mut eight = map.clone();
+        assert_eq!(cardinal.flood_cardinal(Point { x: 0, y: 0 }, b'.', b'O'), 3);
+        assert_eq!(cardinal.get_at_unchecked(Point { x: 2, y: 2 }), b'.');
+
+        assert_eq!(map.flood_eight(Point { x: 0, y: 0 }, b'.', b'O'), 17);
+        assert_eq!(map.find(b'.'), vec![]);
+
+        let dirs = Connectivity::Eight.dirs();
+        let filled = eight.flood_dirs_with(
+            Point { x: 0, y: 0 },
+            dirs,
+            &mut |_, c| c == b'.',
+            &mut |_, _| b'O',
+        );
+        assert_eq!(filled, 17);
+        assert_eq!(eight, map);
+    }
+}
@@ -0,0 +1,443 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, Length, LengthType, Map, Point};
+use num::*;
+
+/// Grid stores a value of any type per tile.
+///
+/// It works like `Map`, but isn't limited to `u8` tiles.
+///
+/// # Example
+///
+/// ```
+/// # use advent_of_tools::*;
+///
+/// let map = Map::<i32>::from_string("0123\n4567\n");
+/// let heights = Grid::from_map(&map, |tile| tile - b'0');
+/// assert_eq!(heights.get_at(Point { x: 1, y: 1 }), Some(&5));
+/// assert_eq!(heights.find(&3), vec![Point { x: 3, y: 0 }]);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<V, T: LengthType = Length>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    data: Vec<V>,
+    width: T,
+    height: T,
+    has_border: bool,
+}
+
+/// Iterator over all positions in a Grid.
+pub struct GridIterator<'a, V, T: LengthType>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    grid: &'a Grid<V, T>,
+    pos: Point<T>,
+}
+
+impl<'a, V, T: LengthType> GridIterator<'a, V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Create an iterator starting at the top left corner.
+    pub fn new(grid: &'a Grid<V, T>) -> Self {
+        Self {
+            grid,
+            pos: Point::<T> {
+                x: Zero::zero(),
+                y: Zero::zero(),
+            },
+        }
+    }
+}
+
+impl<'a, V, T: LengthType> Iterator for GridIterator<'a, V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    type Item = (Point<T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos.x >= self.grid.get_width() {
+            self.pos.x = Zero::zero();
+            self.pos.y += One::one();
+        }
+        if self.pos.y >= self.grid.get_height() {
+            None
+        } else {
+            let pos = self.pos;
+            self.pos.x += One::one();
+            Some((pos, self.grid.get_at_unchecked(pos)))
+        }
+    }
+}
+
+/// Iterator over the neighbors of a position in a Grid.
+pub struct GridNeighborIterator<'a, V, T: LengthType>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    grid: &'a Grid<V, T>,
+    pos: Point<T>,
+    dir: Dir,
+}
+
+impl<'a, V, T: LengthType> GridNeighborIterator<'a, V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Create an iterator over the neighbors of `pos`.
+    pub fn new(grid: &'a Grid<V, T>, pos: Point<T>) -> Self {
+        Self {
+            grid,
+            pos,
+            dir: Dir::North,
+        }
+    }
+}
+
+impl<'a, V, T: LengthType> Iterator for GridNeighborIterator<'a, V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    type Item = (Point<T>, Dir, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.dir == Dir::None {
+                return None;
+            } else {
+                let dir = self.dir;
+                self.dir = self.dir.turn_right();
+                if self.dir == Dir::North {
+                    self.dir = Dir::None;
+                }
+
+                let pos = self.pos.walk(dir);
+                if self.grid.is_inside_grid(pos) {
+                    return Some((pos, dir, self.grid.get_at_unchecked(pos)));
+                }
+            }
+        }
+    }
+}
+
+impl<V, T: LengthType> Grid<V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Create a new grid of given dimensions, filled with `tile`.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let grid: Grid<Option<u32>, i32> = Grid::new(3, 2, None);
+    ///
+    /// assert_eq!(grid.iter().count(), 6);
+    /// ```
+    pub fn new(width: T, height: T, tile: V) -> Self
+    where
+        V: Clone,
+    {
+        Self {
+            data: vec![tile; usize::try_from(width * height).expect("Positive size")],
+            width,
+            height,
+            has_border: false,
+        }
+    }
+
+    /// Create a Grid from a Map.
+    ///
+    /// Each tile of the map is converted with `f`.
+    /// If the map has a border, so will the grid.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Tile {
+    ///     Wall,
+    ///     Open,
+    /// }
+    ///
+    /// let map = Map::<i32>::from_string("#.\n.#\n");
+    /// let grid = Grid::from_map(&map, |tile| if tile == b'#' { Tile::Wall } else { Tile::Open });
+    ///
+    /// assert_eq!(grid.get_at_unchecked(Point { x: 1, y: 0 }), &Tile::Open);
+    /// ```
+    pub fn from_map<F>(map: &Map<T>, f: F) -> Self
+    where
+        F: FnMut(u8) -> V,
+    {
+        Self {
+            data: map.data.iter().copied().map(f).collect(),
+            width: map.width,
+            height: map.height,
+            has_border: map.has_border,
+        }
+    }
+
+    /// Get the width of Grid.
+    ///
+    /// The width includes the border.
+    pub fn get_width(&self) -> T {
+        self.width
+    }
+
+    /// Get the height of Grid.
+    ///
+    /// The height includes the border.
+    pub fn get_height(&self) -> T {
+        self.height
+    }
+
+    /// Returns true if the grid has a border.
+    pub fn has_border(&self) -> bool {
+        self.has_border
+    }
+
+    fn get_index_for(&self, pos: Point<T>) -> usize {
+        usize::try_from(pos.x + pos.y * self.width).expect("Positive index")
+    }
+
+    /// Get the tile at a valid position.
+    pub fn get_at_unchecked(&self, pos: Point<T>) -> &V {
+        &self.data[self.get_index_for(pos)]
+    }
+
+    /// Get the tile at a position.
+    ///
+    /// Returns None if the position isn't valid.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let grid = Grid::new(2, 2, 0u64);
+    ///
+    /// assert_eq!(grid.get_at(Point { x: 1, y: 1 }), Some(&0));
+    /// assert_eq!(grid.get_at(Point { x: 0, y: 2 }), None);
+    /// ```
+    pub fn get_at(&self, pos: Point<T>) -> Option<&V> {
+        if self.is_inside_grid(pos) {
+            Some(&self.data[self.get_index_for(pos)])
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the tile at a position.
+    ///
+    /// Returns None if the position isn't valid.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut visits = Grid::new(2, 2, 0u64);
+    ///
+    /// *visits.get_at_mut(Point { x: 1, y: 0 }).unwrap() += 1;
+    /// assert_eq!(visits.get_at(Point { x: 1, y: 0 }), Some(&1));
+    /// ```
+    pub fn get_at_mut(&mut self, pos: Point<T>) -> Option<&mut V> {
+        if self.is_inside_grid(pos) {
+            let index = self.get_index_for(pos);
+            Some(&mut self.data[index])
+        } else {
+            None
+        }
+    }
+
+    /// Set the tile at a position.
+    ///
+    /// The position is assumed to be valid.
+    /// It can set tiles in the border as well.
+    pub fn set_at(&mut self, pos: Point<T>, val: V) {
+        let index = self.get_index_for(pos);
+        self.data[index] = val
+    }
+
+    /// Add a border around the grid.
+    ///
+    /// Works like `Map::add_border`, the outermost tiles are set to `tile`
+    /// and the grid keeps its size.
+    /// Like for `Map`, it does not make `transform` treat the grid as having a border.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut grid = Grid::new(3, 3, 1);
+    ///
+    /// grid.add_border(0);
+    /// assert_eq!(grid.iter().filter(|&(_pos, &tile)| tile == 1).count(), 1);
+    /// ```
+    pub fn add_border(&mut self, tile: V)
+    where
+        V: Clone,
+    {
+        for y in range(Zero::zero(), self.get_height()) {
+            self.set_at(Point { x: Zero::zero(), y }, tile.clone());
+            self.set_at(
+                Point {
+                    x: self.get_width() - One::one(),
+                    y,
+                },
+                tile.clone(),
+            );
+        }
+        for x in range(Zero::zero(), self.get_width()) {
+            self.set_at(Point { x, y: Zero::zero() }, tile.clone());
+            self.set_at(
+                Point {
+                    x,
+                    y: self.get_height() - One::one(),
+                },
+                tile.clone(),
+            );
+        }
+    }
+
+    /// Iterate over all positions in the grid.
+    ///
+    /// The iterator returns a tuple of the position's Point and the tile.
+    pub fn iter(&self) -> GridIterator<'_, V, T> {
+        GridIterator::new(self)
+    }
+
+    /// Iterate over all neigbors to a position in the grid.
+    ///
+    /// The iterator returns a tuple of the neighbor's Point,
+    /// the direction to it and the tile.
+    /// All valid of the 8 neighbors are given.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let grid = Grid::new(3, 3, 'x');
+    ///
+    /// assert_eq!(grid.neighbors(Point { x: 1, y: 1 }).count(), 8);
+    /// assert_eq!(grid.neighbors(Point { x: 0, y: 1 }).count(), 5);
+    /// ```
+    pub fn neighbors(&self, pos: Point<T>) -> GridNeighborIterator<'_, V, T> {
+        GridNeighborIterator::new(self, pos)
+    }
+
+    /// Update all tiles with a given area.
+    ///
+    /// Works like `Map::transform_area`.
+    ///
+    /// The function returns true if any tiles were changed.
+    pub fn transform_area<F>(&mut self, from: Point<T>, to: Point<T>, mut f: F) -> bool
+    where
+        F: FnMut(&Self, Point<T>, &V) -> V,
+        V: PartialEq,
+    {
+        let mut new_tiles = Vec::new();
+        for (pos, tile) in self.iter() {
+            if pos.x >= from.x && pos.y >= from.y && pos.x < to.x && pos.y < to.y {
+                let new_tile = f(self, pos, tile);
+                if new_tile != *tile {
+                    new_tiles.push((pos, new_tile));
+                }
+            }
+        }
+        let any_change = !new_tiles.is_empty();
+        for (pos, tile) in new_tiles {
+            self.set_at(pos, tile);
+        }
+        any_change
+    }
+
+    /// Update all tiles.
+    ///
+    /// Works like `Map::transform`, the border isn't changed.
+    ///
+    /// The function returns true if any tiles were changed.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut grid = Grid::<u32, i32>::new(3, 1, 1);
+    ///
+    /// // Each tile becomes the sum of its neighbors.
+    /// grid.transform(|grid, pos, _tile| grid.neighbors(pos).map(|(_, _, &tile)| tile).sum());
+    /// assert_eq!(grid.iter().map(|(_, &tile)| tile).collect::<Vec<_>>(), vec![1, 2, 1]);
+    /// ```
+    pub fn transform<F>(&mut self, f: F) -> bool
+    where
+        F: FnMut(&Self, Point<T>, &V) -> V,
+        V: PartialEq,
+    {
+        if self.has_border {
+            self.transform_area(
+                Point::<T> {
+                    x: One::one(),
+                    y: One::one(),
+                },
+                Point::<T> {
+                    x: self.width - One::one(),
+                    y: self.height - One::one(),
+                },
+                f,
+            )
+        } else {
+            self.transform_area(
+                Point::<T> {
+                    x: Zero::zero(),
+                    y: Zero::zero(),
+                },
+                Point::<T> {
+                    x: self.width,
+                    y: self.height,
+                },
+                f,
+            )
+        }
+    }
+
+    /// Check if given position is within the Grid's valid area.
+    ///
+    /// If the grid has a border, its positions are also valid.
+    pub fn is_inside_grid(&self, pos: Point<T>) -> bool {
+        pos.x >= Zero::zero()
+            && pos.y >= Zero::zero()
+            && pos.x < self.get_width()
+            && pos.y < self.get_height()
+    }
+
+    /// Finds all tiles matching `needle`.
+    pub fn find(&self, needle: &V) -> Vec<Point<T>>
+    where
+        V: PartialEq,
+    {
+        self.iter()
+            .filter_map(|(p, c)| if c == needle { Some(p) } else { None })
+            .collect()
+    }
+}
+
+impl<'a, V, T: LengthType> IntoIterator for &'a Grid<V, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    type Item = (Point<T>, &'a V);
+    type IntoIter = GridIterator<'a, V, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
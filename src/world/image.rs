@@ -0,0 +1,286 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use num::{range, Zero};
+use std::io::{self, Write};
+
+/// The PNG file signature.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// The largest amount of data in a stored deflate block.
+const MAX_STORED_BLOCK: usize = 65535;
+
+/// Lookup table for `crc32`.
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+/// The CRC-32 checksum used by PNG chunks.
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = 0xffffffff;
+    for &part in parts {
+        for &byte in part {
+            crc = CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8);
+        }
+    }
+    crc ^ 0xffffffff
+}
+
+/// The Adler-32 checksum used by zlib streams.
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1, 0);
+    // 5552 is the most bytes that can be summed before b can overflow.
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+/// Wrap `data` in a zlib stream of stored, uncompressed, deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED_BLOCK).max(1);
+    let mut stream = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    stream.extend_from_slice(&[0x78, 0x01]);
+    let mut chunks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let is_final = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        stream.push(u8::from(is_final));
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(chunk);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+/// Write a PNG chunk with its length and checksum.
+fn write_png_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Too large PNG chunk"))?;
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the image's width, height and rows of RGB pixels.
+    ///
+    /// Every row is prefixed with `row_prefix`, if any.
+    fn pixels<F>(&self, scale: usize, row_prefix: Option<u8>, mut f: F) -> (usize, usize, Vec<u8>)
+    where
+        F: FnMut(Point<T>, u8) -> [u8; 3],
+    {
+        let width = usize::try_from(self.width).expect("Positive width") * scale;
+        let height = usize::try_from(self.height).expect("Positive height") * scale;
+        let row_len = width * 3 + usize::from(row_prefix.is_some());
+        let mut data = Vec::with_capacity(row_len * height);
+        let mut row = Vec::with_capacity(row_len);
+        for y in range(Zero::zero(), self.height) {
+            row.clear();
+            row.extend(row_prefix);
+            for x in range(Zero::zero(), self.width) {
+                let pos = Point { x, y };
+                let rgb = f(pos, self.get_at_unchecked(pos));
+                for _ in 0..scale {
+                    row.extend_from_slice(&rgb);
+                }
+            }
+            for _ in 0..scale {
+                data.extend_from_slice(&row);
+            }
+        }
+        (width, height, data)
+    }
+
+    /// Write the map as a binary PPM image.
+    ///
+    /// `f` gets called with the position and the tile and returns
+    /// the tile's color as red, green and blue.
+    /// Every tile is drawn as `scale` x `scale` pixels.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("#.\n");
+    /// let mut out = Vec::new();
+    ///
+    /// map.write_ppm_with(&mut out, 1, |_pos, tile| if tile == b'#' { [255, 0, 0] } else { [0; 3] })
+    ///     .expect("Writing to a Vec");
+    /// assert_eq!(out, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00");
+    /// ```
+    pub fn write_ppm_with<W, F>(&self, out: &mut W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(Point<T>, u8) -> [u8; 3],
+    {
+        let (width, height, data) = self.pixels(scale, None, f);
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        out.write_all(&data)
+    }
+
+    /// Write the map as a PNG image.
+    ///
+    /// Works like `write_ppm_with`. The image data is stored without compression,
+    /// so the files are about as large as PPM files.
+    ///
+    /// Returns an error of kind `InvalidInput` if the image would be empty or too large.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("#..\n.#.\n..#\n");
+    /// let mut file = std::fs::File::create("map.png").expect("A new file");
+    ///
+    /// map.write_png_with(&mut file, 4, |pos, tile| match tile {
+    ///     b'#' => [255, 255, 255],
+    ///     _ if pos.x == 0 => [0, 0, 255],
+    ///     _ => [0, 0, 0],
+    /// })
+    /// .expect("Writing the image");
+    /// ```
+    pub fn write_png_with<W, F>(&self, out: &mut W, scale: usize, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(Point<T>, u8) -> [u8; 3],
+    {
+        // Filter type 0, no filter, before every row.
+        let (width, height, data) = self.pixels(scale, Some(0), f);
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "Invalid PNG image size");
+        if width == 0 || height == 0 {
+            return Err(invalid());
+        }
+        let width = u32::try_from(width).map_err(|_| invalid())?;
+        let height = u32::try_from(height).map_err(|_| invalid())?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        // 8 bits per sample, RGB, deflate, the standard filters, no interlace.
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        out.write_all(&PNG_SIGNATURE)?;
+        write_png_chunk(out, b"IHDR", &header)?;
+        write_png_chunk(out, b"IDAT", &zlib_stored(&data))?;
+        write_png_chunk(out, b"IEND", &[])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(&[b"1234", b"56789"]), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    /// Decode the rows of a PNG image written by `write_png_with`.
+    fn decode_png(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        assert_eq!(png[..8], PNG_SIGNATURE);
+        let mut rest = &png[8..];
+        let mut header = Vec::new();
+        let mut zlib = Vec::new();
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&[kind, data]));
+            match kind {
+                b"IHDR" => header = data.to_vec(),
+                b"IDAT" => zlib.extend_from_slice(data),
+                _ => (),
+            }
+            rest = &rest[12 + len..];
+        }
+        let width = u32::from_be_bytes(header[..4].try_into().unwrap());
+        let height = u32::from_be_bytes(header[4..8].try_into().unwrap());
+
+        let mut data = Vec::new();
+        let mut stream = &zlib[2..];
+        loop {
+            let is_final = stream[0] == 1;
+            let len = u16::from_le_bytes([stream[1], stream[2]]);
+            assert_eq!(!len, u16::from_le_bytes([stream[3], stream[4]]));
+            data.extend_from_slice(&stream[5..5 + len as usize]);
+            stream = &stream[5 + len as usize..];
+            if is_final {
+                break;
+            }
+        }
+        assert_eq!(stream, adler32(&data).to_be_bytes());
+        (width, height, data)
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let map = Map::<i32>::from_string("#.\n.#\n");
+        let mut png = Vec::new();
+        map.write_png_with(&mut png, 2, |_pos, tile| [tile, 0, 1])
+            .unwrap();
+
+        let (width, height, data) = decode_png(&png);
+        assert_eq!((width, height), (4, 4));
+        let (h, d) = (b'#', b'.');
+        let row = |a: u8, b: u8| vec![0, a, 0, 1, a, 0, 1, b, 0, 1, b, 0, 1];
+        let expected = [row(h, d), row(h, d), row(d, h), row(d, h)].concat();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_png_with_many_blocks() {
+        let map = Map::<i32>::new(300, 100);
+        let mut png = Vec::new();
+        map.write_png_with(&mut png, 1, |pos, _tile| [pos.x as u8, pos.y as u8, 7])
+            .unwrap();
+
+        let (width, height, data) = decode_png(&png);
+        assert_eq!((width, height), (300, 100));
+        assert_eq!(data.len(), (300 * 3 + 1) * 100);
+        assert_eq!(data[(300 * 3 + 1) * 99 + 1..][..6], [0, 99, 7, 1, 99, 7]);
+    }
+
+    #[test]
+    fn test_png_of_empty_map() {
+        let map = Map::<i32>::new(0, 0);
+        let err = map.write_png_with(&mut Vec::new(), 1, |_, _| [0; 3]);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,188 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use num::*;
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Create a new map of the given size where each tile is copied from
+    /// the position in this map returned by `from`.
+    ///
+    /// If the map has a border, the new map's border is redrawn from this map's
+    /// corners, top edge and left edge, so b'-' stays along the top and bottom etc.
+    fn remap<F>(&self, width: T, height: T, from: F) -> Self
+    where
+        F: Fn(Point<T>) -> Point<T>,
+    {
+        let mut data = Vec::with_capacity(self.data.len());
+        for y in range(Zero::zero(), height) {
+            for x in range(Zero::zero(), width) {
+                data.push(self.get_at_unchecked(from(Point { x, y })));
+            }
+        }
+        let mut map = Self {
+            data,
+            width,
+            height,
+            has_border: self.has_border,
+        };
+        if self.has_border {
+            let zero = Zero::zero();
+            let one = One::one();
+            let horizontal = self.get_at_unchecked(Point { x: one, y: zero });
+            let vertical = self.get_at_unchecked(Point { x: zero, y: one });
+            for x in range(zero, width) {
+                map.set_at(Point { x, y: zero }, horizontal);
+                map.set_at(Point { x, y: height - one }, horizontal);
+            }
+            for y in range(zero, height) {
+                map.set_at(Point { x: zero, y }, vertical);
+                map.set_at(Point { x: width - one, y }, vertical);
+            }
+            for (x, old_x) in [(zero, zero), (width - one, self.width - one)] {
+                for (y, old_y) in [(zero, zero), (height - one, self.height - one)] {
+                    map.set_at(
+                        Point { x, y },
+                        self.get_at_unchecked(Point { x: old_x, y: old_y }),
+                    );
+                }
+            }
+        }
+        map
+    }
+
+    /// Returns a copy of the map rotated 90 degrees clockwise.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.rotate_right(), Map::from_string("da\neb\nfc\n"));
+    ///
+    /// let map = Map::<i32>::from_string_with_border("abc\ndef\n");
+    /// assert_eq!(map.rotate_right(), Map::from_string_with_border("da\neb\nfc\n"));
+    /// ```
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |pos| Point {
+            x: pos.y,
+            y: height - One::one() - pos.x,
+        })
+    }
+
+    /// Returns a copy of the map rotated 90 degrees counterclockwise.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.rotate_left(), Map::from_string("cf\nbe\nad\n"));
+    /// ```
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |pos| Point {
+            x: width - One::one() - pos.y,
+            y: pos.x,
+        })
+    }
+
+    /// Returns a copy of the map rotated 180 degrees.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.rotate_180(), Map::from_string("fed\ncba\n"));
+    /// ```
+    pub fn rotate_180(&self) -> Self {
+        let (width, height) = (self.width, self.height);
+        self.remap(width, height, |pos| Point {
+            x: width - One::one() - pos.x,
+            y: height - One::one() - pos.y,
+        })
+    }
+
+    /// Returns a copy of the map mirrored left to right.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.flip_horizontal(), Map::from_string("cba\nfed\n"));
+    /// ```
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remap(width, self.height, |pos| Point {
+            x: width - One::one() - pos.x,
+            y: pos.y,
+        })
+    }
+
+    /// Returns a copy of the map mirrored top to bottom.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.flip_vertical(), Map::from_string("def\nabc\n"));
+    /// ```
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.remap(self.width, height, |pos| Point {
+            x: pos.x,
+            y: height - One::one() - pos.y,
+        })
+    }
+
+    /// Returns a copy of the map mirrored along the diagonal from the top left corner.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef\n");
+    ///
+    /// assert_eq!(map.transpose(), Map::from_string("ad\nbe\ncf\n"));
+    /// ```
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |pos| Point { x: pos.y, y: pos.x })
+    }
+
+    /// Returns the map in all of its 8 orientations.
+    ///
+    /// The first four are the map rotated 0, 90, 180 and 270 degrees clockwise,
+    /// the last four are the same for the horizontally flipped map.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("ab\ncd\n");
+    /// let orientations = map.orientations();
+    ///
+    /// assert_eq!(orientations.len(), 8);
+    /// assert!(orientations.contains(&map.transpose()));
+    /// assert_eq!(orientations[0], map);
+    /// ```
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations = Vec::with_capacity(8);
+        for mut map in [self.clone(), self.flip_horizontal()] {
+            for _ in 0..4 {
+                let next = map.rotate_right();
+                orientations.push(map);
+                map = next;
+            }
+        }
+        orientations
+    }
+}
@@ -0,0 +1,452 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map};
+
+/// The reasons a Map couldn't be parsed.
+///
+/// Line and column numbers start at one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MapParseError {
+    /// There were no tiles in the input.
+    Empty,
+    /// A line's width differs from the first line's.
+    RaggedLine {
+        /// The line number.
+        line: usize,
+        /// The width of the first line.
+        expected: usize,
+        /// The width of this line.
+        found: usize,
+    },
+    /// A character that isn't ASCII was found.
+    InvalidChar {
+        /// The line number.
+        line: usize,
+        /// The column number.
+        column: usize,
+        /// The character.
+        found: char,
+    },
+    /// The map's size doesn't fit in its length type.
+    TooLarge {
+        /// The width of the map.
+        width: usize,
+        /// The height of the map.
+        height: usize,
+    },
+}
+
+impl std::fmt::Display for MapParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use MapParseError::*;
+        match self {
+            Empty => write!(f, "The map is empty"),
+            RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {line} is {found} characters wide, expected {expected}"
+            ),
+            InvalidChar {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "Invalid character {found:?} at line {line}, column {column}"
+            ),
+            TooLarge { width, height } => {
+                write!(f, "The map's size {width}x{height} is too large")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapParseError {}
+
+impl MapParseError {
+    /// Move the error's line number forward by `lines`.
+    fn offset_lines(self, lines: usize) -> Self {
+        use MapParseError::*;
+        match self {
+            RaggedLine {
+                line,
+                expected,
+                found,
+            } => RaggedLine {
+                line: line + lines,
+                expected,
+                found,
+            },
+            InvalidChar {
+                line,
+                column,
+                found,
+            } => InvalidChar {
+                line: line + lines,
+                column,
+                found,
+            },
+            other => other,
+        }
+    }
+}
+
+/// Options for how `Map::try_from_str_with_options` parses maps.
+///
+/// Both `\n` and `\r\n` line endings are always accepted and
+/// leading and trailing empty lines are always ignored.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct MapParseOptions {
+    /// Remove whitespace at the end of each line.
+    pub trim_trailing_whitespace: bool,
+    /// Pad lines to the widest line's width with this tile,
+    /// instead of reporting them as ragged.
+    pub pad_with: Option<u8>,
+    /// Add a border around the map, like `Map::from_string_with_border`.
+    pub border: bool,
+}
+
+/// Split the input into the map's lines.
+///
+/// Returns the number of skipped leading empty lines together with the lines.
+fn split_lines<'a>(s: &'a str, options: &MapParseOptions) -> (usize, Vec<&'a str>) {
+    let mut lines: Vec<_> = s
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .map(|line| {
+            if options.trim_trailing_whitespace {
+                line.trim_end()
+            } else {
+                line
+            }
+        })
+        .collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let skipped = lines.iter().take_while(|line| line.is_empty()).count();
+    lines.drain(..skipped);
+    (skipped, lines)
+}
+
+/// A block of lines, separated from the rest of the input by blank lines.
+struct Block<'a> {
+    /// The number of blank lines before the block.
+    skipped_lines: usize,
+    /// The block's lines.
+    text: &'a str,
+    /// The number of lines in the block.
+    lines: usize,
+    /// The number of lines in the block and the blank lines around it.
+    consumed_lines: usize,
+    /// The input after the block and the blank lines following it.
+    rest: &'a str,
+}
+
+/// Split off the first block of non-blank lines from `s`.
+fn split_block(s: &str) -> Block<'_> {
+    let is_blank = |line: &str| line.trim().is_empty();
+    let mut skipped_lines = 0;
+    let mut start = 0;
+    let mut end = None;
+    let mut lines = 0;
+    let mut consumed_lines = 0;
+    let mut pos = 0;
+    for line in s.split_inclusive('\n') {
+        match (end, is_blank(line)) {
+            (None, true) if lines == 0 => {
+                skipped_lines += 1;
+                start = pos + line.len();
+            }
+            (None, true) => end = Some(pos),
+            (None, false) => lines += 1,
+            (Some(_), true) => (),
+            (Some(_), false) => break,
+        }
+        pos += line.len();
+        consumed_lines += 1;
+    }
+    let end = end.unwrap_or(pos);
+    Block {
+        skipped_lines,
+        text: &s[start..end],
+        lines,
+        consumed_lines,
+        rest: &s[pos..],
+    }
+}
+
+/// Check the lines of a map and return its width.
+fn check_lines(lines: &[&str], options: &MapParseOptions) -> Result<usize, MapParseError> {
+    let width = if options.pad_with.is_some() {
+        lines.iter().map(|line| line.len()).max().unwrap_or(0)
+    } else {
+        lines.first().map_or(0, |line| line.len())
+    };
+    for (line_no, line) in lines.iter().enumerate() {
+        if let Some((column, found)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(MapParseError::InvalidChar {
+                line: line_no + 1,
+                column: column + 1,
+                found,
+            });
+        }
+        if line.len() != width && options.pad_with.is_none() {
+            return Err(MapParseError::RaggedLine {
+                line: line_no + 1,
+                expected: width,
+                found: line.len(),
+            });
+        }
+    }
+    if width == 0 {
+        return Err(MapParseError::Empty);
+    }
+    Ok(width)
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    T: TryFrom<usize>,
+{
+    fn from_parts(
+        data: Vec<u8>,
+        width: usize,
+        height: usize,
+        has_border: bool,
+    ) -> Result<Self, MapParseError> {
+        let too_large = MapParseError::TooLarge { width, height };
+        let size = width.checked_mul(height).ok_or(too_large.clone())?;
+        T::try_from(size).map_err(|_| too_large.clone())?;
+        Ok(Self {
+            data,
+            width: T::try_from(width).map_err(|_| too_large.clone())?,
+            height: T::try_from(height).map_err(|_| too_large)?,
+            has_border,
+        })
+    }
+
+    /// Try to create a Map from a string.
+    ///
+    /// Each line becomes a row in the map.
+    /// All lines must be of equal width and only contain ASCII characters.
+    /// Trailing empty lines are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::try_from_str("ab\r\ncd\r\n\r").expect("A valid map");
+    /// assert_eq!(map.get_width(), 2);
+    /// assert_eq!(map.get_height(), 2);
+    ///
+    /// assert_eq!(
+    ///     Map::<i32>::try_from_str("ab\nc\n"),
+    ///     Err(MapParseError::RaggedLine { line: 2, expected: 2, found: 1 })
+    /// );
+    /// assert_eq!(
+    ///     Map::<i32>::try_from_str("ab\ncö\n"),
+    ///     Err(MapParseError::InvalidChar { line: 2, column: 2, found: 'ö' })
+    /// );
+    /// assert_eq!(Map::<i32>::try_from_str(""), Err(MapParseError::Empty));
+    /// assert_eq!(
+    ///     Map::<i8>::try_from_str(&"x".repeat(200)),
+    ///     Err(MapParseError::TooLarge { width: 200, height: 1 })
+    /// );
+    /// ```
+    pub fn try_from_str(s: &str) -> Result<Self, MapParseError> {
+        Self::try_from_str_with_options(s, &MapParseOptions::default())
+    }
+
+    /// Try to create a Map from a string with a border.
+    ///
+    /// Works like `try_from_str`, but the map gets a border around it
+    /// like with `from_string_with_border`.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::try_from_str_with_border("ab\ncd\n\n").expect("A valid map");
+    /// assert_eq!(map.get_width(), 4);
+    /// assert_eq!(map.get_height(), 4);
+    /// assert_eq!(map.get_at(Point { x: 3, y: 2 }), Some(b'|'));
+    /// assert_eq!(map.get_at(Point { x: 1, y: 3 }), Some(b'-'));
+    /// ```
+    pub fn try_from_str_with_border(s: &str) -> Result<Self, MapParseError> {
+        Self::try_from_str_with_options(
+            s,
+            &MapParseOptions {
+                border: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Try to create a Map from a string, parsed according to `options`.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let options = MapParseOptions {
+    ///     trim_trailing_whitespace: true,
+    ///     pad_with: Some(b' '),
+    ///     ..Default::default()
+    /// };
+    /// let map = Map::<i32>::try_from_str_with_options("  ..#\n  .  \n....#.\n", &options)
+    ///     .expect("A valid map");
+    /// assert_eq!(map.get_width(), 6);
+    /// assert_eq!(map.get_at(Point { x: 5, y: 0 }), Some(b' '));
+    /// assert_eq!(map.get_at(Point { x: 2, y: 1 }), Some(b'.'));
+    /// ```
+    pub fn try_from_str_with_options(
+        s: &str,
+        options: &MapParseOptions,
+    ) -> Result<Self, MapParseError> {
+        let (skipped, lines) = split_lines(s, options);
+        let border = if options.border { 1 } else { 0 };
+        let inner_width = check_lines(&lines, options).map_err(|err| err.offset_lines(skipped))?;
+        let width = inner_width + 2 * border;
+        let height = lines.len() + 2 * border;
+        let pad = options.pad_with.unwrap_or(b' ');
+
+        let mut data = Vec::with_capacity(height * width);
+        let horizontal_edge = |data: &mut Vec<u8>| {
+            data.push(b'+');
+            data.resize(data.len() + inner_width, b'-');
+            data.push(b'+');
+        };
+        if options.border {
+            horizontal_edge(&mut data);
+        }
+        for line in &lines {
+            if options.border {
+                data.push(b'|');
+            }
+            data.extend_from_slice(line.as_bytes());
+            data.resize(data.len() + inner_width - line.len(), pad);
+            if options.border {
+                data.push(b'|');
+            }
+        }
+        if options.border {
+            horizontal_edge(&mut data);
+        }
+        Self::from_parts(data, width, height, options.border)
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    T: TryFrom<usize>,
+    <T as TryFrom<usize>>::Error: std::fmt::Debug,
+{
+    /// Try to create Maps from a string with several maps separated by blank lines.
+    ///
+    /// The line numbers in errors are counted from the start of `s`.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let maps = Map::<i32>::try_many_from_str("#.\n.#\n\n###\n...\n").expect("Valid maps");
+    /// assert_eq!(maps.len(), 2);
+    /// assert_eq!(maps[1].get_width(), 3);
+    ///
+    /// assert_eq!(
+    ///     Map::<i32>::try_many_from_str("#.\n.#\n\n###\n..\n"),
+    ///     Err(MapParseError::RaggedLine { line: 5, expected: 3, found: 2 })
+    /// );
+    /// ```
+    pub fn try_many_from_str(s: &str) -> Result<Vec<Self>, MapParseError> {
+        let mut maps = Vec::new();
+        let mut line = 0;
+        let mut rest = s;
+        loop {
+            let block = split_block(rest);
+            if block.lines == 0 {
+                return Ok(maps);
+            }
+            let offset = line + block.skipped_lines;
+            maps.push(Self::try_from_str(block.text).map_err(|err| err.offset_lines(offset))?);
+            line += block.consumed_lines;
+            rest = block.rest;
+        }
+    }
+
+    /// Create Maps from a string with several maps separated by blank lines.
+    ///
+    /// Panics if any of the maps can't be parsed, see `try_many_from_str`.
+    pub fn many_from_string(s: &str) -> Vec<Self> {
+        Self::try_many_from_str(s).unwrap_or_else(|err| panic!("Invalid map: {err}"))
+    }
+
+    /// Try to create a Map from the first block of lines in a string.
+    ///
+    /// The map ends at the first blank line. The rest of the input,
+    /// after the blank lines, is returned together with the map.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let (map, moves) = Map::<i32>::try_from_str_with_rest("#.\n.@\n\n<^^>\n>v\n").expect("A valid map");
+    /// assert_eq!(map.find(b'@'), vec![Point { x: 1, y: 1 }]);
+    /// assert_eq!(moves, "<^^>\n>v\n");
+    /// ```
+    pub fn try_from_str_with_rest(s: &str) -> Result<(Self, &str), MapParseError> {
+        let block = split_block(s);
+        let map =
+            Self::try_from_str(block.text).map_err(|err| err.offset_lines(block.skipped_lines))?;
+        Ok((map, block.rest))
+    }
+
+    /// Create a Map from the first block of lines in a string.
+    ///
+    /// Panics if the map can't be parsed, see `try_from_str_with_rest`.
+    pub fn from_string_with_rest(s: &str) -> (Self, &str) {
+        Self::try_from_str_with_rest(s).unwrap_or_else(|err| panic!("Invalid map: {err}"))
+    }
+}
+
+impl<T: LengthType> std::str::FromStr for Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    T: TryFrom<usize>,
+{
+    type Err = MapParseError;
+
+    /// Parse a Map with `Map::try_from_str`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_leading_empty_lines() {
+        let map = Map::<i32>::try_from_str("\n\nab\ncd\n").expect("A valid map");
+        assert_eq!(map, Map::from_string("ab\ncd\n"));
+
+        // Line numbers still count the skipped lines.
+        assert_eq!(
+            Map::<i32>::try_from_str("\nab\nc\n"),
+            Err(MapParseError::RaggedLine {
+                line: 3,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::fill::{scanline_fill, Connectivity};
+use super::{Dir, Grid, LengthType, Map, Point, CARDINALS};
+
+/// Statistics about a region of equal, connected tiles.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegionStats<T> {
+    /// The region's tile.
+    pub tile: u8,
+    /// The first position of the region, in reading order.
+    pub start: Point<T>,
+    /// The number of positions in the region.
+    pub area: usize,
+    /// The number of tile edges between the region and other tiles.
+    pub perimeter: usize,
+    /// The number of straight sides of the region, the same as its number of corners.
+    pub sides: usize,
+}
+
+/// The regions of a Map, created by `Map::regions`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Regions<T: LengthType>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// The index into `stats` for each position.
+    ///
+    /// The border, if any, has no region.
+    pub labels: Grid<Option<usize>, T>,
+    /// The statistics of each region.
+    pub stats: Vec<RegionStats<T>>,
+}
+
+impl<T: LengthType> Regions<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the statistics of the region at a position.
+    pub fn region_at(&self, pos: Point<T>) -> Option<&RegionStats<T>> {
+        self.labels
+            .get_at(pos)
+            .copied()
+            .flatten()
+            .map(|label| &self.stats[label])
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Find all regions of equal, connected tiles.
+    ///
+    /// The map isn't changed. The border, if any, isn't part of any region.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("AAAA\nBBCD\nBBCC\nEEEC\n");
+    ///
+    /// let regions = map.regions(Connectivity::Cardinal);
+    /// assert_eq!(regions.stats.len(), 5);
+    ///
+    /// let c = regions.region_at(Point { x: 2, y: 1 }).expect("A region");
+    /// assert_eq!(c.tile, b'C');
+    /// assert_eq!(c.area, 4);
+    /// assert_eq!(c.perimeter, 10);
+    /// assert_eq!(c.sides, 8);
+    ///
+    /// let a = regions.region_at(Point { x: 0, y: 0 }).expect("A region");
+    /// assert_eq!((a.area, a.perimeter, a.sides), (4, 10, 4));
+    /// ```
+    pub fn regions(&self, connectivity: Connectivity) -> Regions<T> {
+        let area = self.inner_area();
+        let mut labels = Grid::new(self.width, self.height, None);
+        let mut stats = Vec::new();
+        for (pos, tile) in self.iter() {
+            if pos.x < area.0.x || pos.y < area.0.y || pos.x >= area.1.x || pos.y >= area.1.y {
+                continue;
+            }
+            if labels.get_at_unchecked(pos).is_some() {
+                continue;
+            }
+            let label = stats.len();
+            let size = scanline_fill(
+                &mut labels,
+                pos,
+                area,
+                connectivity == Connectivity::Eight,
+                |labels, pos| {
+                    labels.get_at_unchecked(pos).is_none() && self.get_at_unchecked(pos) == tile
+                },
+                |labels, pos| labels.set_at(pos, Some(label)),
+            );
+            stats.push(RegionStats {
+                tile,
+                start: pos,
+                area: size,
+                perimeter: 0,
+                sides: 0,
+            });
+        }
+
+        for (pos, &label) in labels.iter() {
+            let Some(label) = label else {
+                continue;
+            };
+            let same = |dir: Dir| labels.get_at(pos.walk(dir)) == Some(&Some(label));
+            let stats = &mut stats[label];
+            for dir in CARDINALS {
+                if !same(dir) {
+                    stats.perimeter += 1;
+                }
+                let next = dir.turn_cardinal_right();
+                let diagonal = dir.turn_right();
+                if (!same(dir) && !same(next)) || (same(dir) && same(next) && !same(diagonal)) {
+                    stats.sides += 1;
+                }
+            }
+        }
+
+        Regions { labels, stats }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GARDEN: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    #[test]
+    fn test_regions_prices() {
+        let map = Map::<i32>::from_string(GARDEN);
+        let regions = map.regions(Connectivity::Cardinal);
+        assert_eq!(regions.stats.len(), 11);
+        let price: usize = regions.stats.iter().map(|r| r.area * r.perimeter).sum();
+        assert_eq!(price, 1930);
+        let price: usize = regions.stats.iter().map(|r| r.area * r.sides).sum();
+        assert_eq!(price, 1206);
+    }
+
+    #[test]
+    fn test_regions_eight_connected() {
+        let map = Map::<i32>::from_string_with_border("#..\n.#.\n..#\n");
+        let regions = map.regions(Connectivity::Eight);
+        assert_eq!(regions.stats.len(), 2);
+        assert_eq!(regions.region_at(Point { x: 1, y: 1 }).unwrap().area, 3);
+        assert_eq!(regions.region_at(Point { x: 0, y: 0 }), None);
+    }
+}
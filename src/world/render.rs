@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point, Style};
+use num::*;
+use std::fmt;
+use std::io;
+
+/// Turn an overlay function into one that draws its tiles with the default style.
+fn plain<T, F>(mut f: F) -> impl FnMut(Point<T>, u8) -> (u8, Style)
+where
+    F: FnMut(Point<T>, u8) -> u8,
+{
+    move |pos, tile| (f(pos, tile), Style::default())
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the area of the map to render, with or without the border.
+    pub(super) fn render_area(&self, with_border: bool) -> (Point<T>, Point<T>) {
+        if with_border {
+            (
+                Point {
+                    x: Zero::zero(),
+                    y: Zero::zero(),
+                },
+                Point {
+                    x: self.width,
+                    y: self.height,
+                },
+            )
+        } else {
+            self.inner_area()
+        }
+    }
+
+    /// Call `write_line` with every row of the map, each ending with a newline.
+    ///
+    /// `f` returns the tile to show and its style. Escape sequences are only
+    /// added when the style changes and every line ends with the default style,
+    /// so nothing but the tiles are written when all tiles have the default style.
+    pub(super) fn write_lines<F, W, E>(
+        &self,
+        with_border: bool,
+        mut f: F,
+        mut write_line: W,
+    ) -> Result<(), E>
+    where
+        F: FnMut(Point<T>, u8) -> (u8, Style),
+        W: FnMut(&str) -> Result<(), E>,
+    {
+        let (from, to) = self.render_area(with_border);
+        let mut line = String::new();
+        for y in range(from.y, to.y) {
+            line.clear();
+            let mut current = Style::default();
+            for x in range(from.x, to.x) {
+                let pos = Point { x, y };
+                let (tile, style) = f(pos, self.get_at_unchecked(pos));
+                if style != current {
+                    line.push_str(&style.escape());
+                    current = style;
+                }
+                line.push(char::from(tile));
+            }
+            if current != Style::default() {
+                line.push_str(&Style::default().escape());
+            }
+            line.push('\n');
+            write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    /// Write the map to `out` with an overlay provided by f.
+    ///
+    /// Works like `print_with_overlay`, except the border, if any,
+    /// is only written when `with_border` is true.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string_with_border("abc\ndef");
+    /// let mut out = Vec::new();
+    ///
+    /// map.write_with_overlay(&mut out, false, |pos, tile| if pos.y == 1 { b'=' } else { tile })
+    ///     .expect("Writing to a Vec");
+    /// assert_eq!(out, b"===\ndef\n");
+    /// ```
+    pub fn write_with_overlay<W, F>(&self, out: &mut W, with_border: bool, f: F) -> io::Result<()>
+    where
+        W: io::Write,
+        F: FnMut(Point<T>, u8) -> u8,
+    {
+        self.write_lines(with_border, plain(f), |line| out.write_all(line.as_bytes()))
+    }
+
+    /// Render the map to a String with an overlay provided by f.
+    ///
+    /// Works like `write_with_overlay`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abc\ndef");
+    ///
+    /// let path = [Point { x: 0, y: 0 }, Point { x: 1, y: 0 }, Point { x: 1, y: 1 }];
+    /// let s = map.to_string_with_overlay(true, |pos, tile| if path.contains(&pos) { b'*' } else { tile });
+    /// assert_eq!(s, "**c\nd*f\n");
+    /// ```
+    pub fn to_string_with_overlay<F>(&self, with_border: bool, f: F) -> String
+    where
+        F: FnMut(Point<T>, u8) -> u8,
+    {
+        let mut s = String::new();
+        self.write_lines(with_border, plain(f), |line| -> fmt::Result {
+            s.push_str(line);
+            Ok(())
+        })
+        .expect("Writing to a String");
+        s
+    }
+}
+
+/// Formats the map as its lines of tiles.
+///
+/// The alternate form, `{:#}`, leaves out the border.
+///
+/// # Example:
+/// ```
+/// # use advent_of_tools::*;
+/// let map = Map::<i32>::from_string_with_border("ab\ncd\n");
+///
+/// assert_eq!(format!("{map}"), "+--+\n|ab|\n|cd|\n+--+\n");
+/// assert_eq!(format!("{map:#}"), "ab\ncd\n");
+/// ```
+impl<T: LengthType> fmt::Display for Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let with_border = !f.alternate();
+        self.write_lines(
+            with_border,
+            |_, tile| (tile, Style::default()),
+            |line| f.write_str(line),
+        )
+    }
+}
@@ -0,0 +1,589 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, Grid, LengthType, Map, Point, PointAndCost, CARDINALS};
+use crate::search::{AllPaths, SearchOutcome, StatePath};
+use num::*;
+use std::collections::BinaryHeap;
+
+/// A path found by one of Map's searches.
+///
+/// `points` starts with the search's start position and ends with its goal.
+/// `dirs` holds the direction taken for each step, so it is one shorter than `points`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MapPath<T, U> {
+    /// The total cost of the path.
+    pub cost: U,
+    /// The positions along the path.
+    pub points: Vec<Point<T>>,
+    /// The direction taken from each position to the next.
+    pub dirs: Vec<Dir>,
+}
+
+impl<T, U: Copy> SearchOutcome<MapPath<T, U>> {
+    /// Returns the found path's cost, or None if the goal was unreachable.
+    pub fn cost(&self) -> Option<U> {
+        self.path.as_ref().map(|path| path.cost)
+    }
+}
+
+/// The rules for `Map::search_turning`.
+///
+/// Turning is done together with the step after it.
+/// Turning around counts as two turns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TurnRules<U> {
+    /// The extra cost of turning 90 degrees.
+    pub turn_cost: U,
+    /// The fewest steps that must be taken in a direction before turning or stopping.
+    pub min_straight: usize,
+    /// The most steps that may be taken in a row in the same direction.
+    pub max_straight: usize,
+    /// If it is allowed to turn around.
+    pub allow_reverse: bool,
+}
+
+impl<U> TurnRules<U> {
+    /// Create rules where turns cost `turn_cost` and there are no limits on straight runs.
+    pub fn new(turn_cost: U) -> Self {
+        Self {
+            turn_cost,
+            min_straight: 0,
+            max_straight: usize::MAX,
+            allow_reverse: true,
+        }
+    }
+}
+
+impl<T: LengthType, U> From<StatePath<Point<T>, U>> for MapPath<T, U> {
+    /// Convert a path found by one of the generic searches.
+    ///
+    /// The directions are derived from the positions,
+    /// which must be neighbors of each other.
+    fn from(path: StatePath<Point<T>, U>) -> Self {
+        let dirs = path
+            .states
+            .windows(2)
+            .map(|pair| dir_between(pair[0], pair[1]))
+            .collect();
+        Self {
+            cost: path.cost,
+            points: path.states,
+            dirs,
+        }
+    }
+}
+
+fn dir_between<T: LengthType>(from: Point<T>, to: Point<T>) -> Dir {
+    let mut dir = Dir::North;
+    loop {
+        if from.walk(dir) == to {
+            return dir;
+        }
+        dir = dir.turn_right();
+        if dir == Dir::North {
+            panic!("{from:?} and {to:?} are not neighbors");
+        }
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Find the cheapest path from `from` to `to`.
+    ///
+    /// `f` is called with the map, the neighbor's position, the direction to it and its tile.
+    /// It returns the cost of stepping there, or None if it can't be entered.
+    ///
+    /// Returns None if `to` can't be reached.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S.#\n#..\n#.E\n");
+    ///
+    /// let path = map
+    ///     .bfs_path(Point { x: 0, y: 0 }, Point { x: 2, y: 2 }, &mut |_map, _pos, dir, tile| {
+    ///         if tile == b'#' || !dir.is_cardinal() {
+    ///             None
+    ///         } else {
+    ///             Some(1)
+    ///         }
+    ///     })
+    ///     .expect("A path");
+    /// assert_eq!(path.cost, 4);
+    /// assert_eq!(path.points.len(), 5);
+    /// assert_eq!(path.dirs[0], Dir::East);
+    /// ```
+    pub fn bfs_path<F, U>(&self, from: Point<T>, to: Point<T>, f: &mut F) -> Option<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        self.search(from, to, f).path
+    }
+
+    /// Search for the cheapest path from `from` to `to`.
+    ///
+    /// Works like `bfs_path`, but the outcome also tells how many
+    /// positions were expanded during the search.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S.#\n###\n#.E\n");
+    ///
+    /// let outcome = map.search(Point { x: 0, y: 0 }, Point { x: 2, y: 2 }, &mut |_map, _pos, _dir, tile| {
+    ///     if tile == b'#' {
+    ///         None
+    ///     } else {
+    ///         Some(1)
+    ///     }
+    /// });
+    /// assert!(!outcome.is_reachable());
+    /// assert_eq!(outcome.cost(), None);
+    /// assert_eq!(outcome.expanded, 2);
+    /// ```
+    pub fn search<F, U>(
+        &self,
+        from: Point<T>,
+        to: Point<T>,
+        f: &mut F,
+    ) -> SearchOutcome<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        self.astar(from, to, f, &mut |_| Zero::zero())
+    }
+
+    /// Search for the cheapest path from `from` to `to` with A*.
+    ///
+    /// `f` works as for `search`, so switching between them only needs
+    /// the extra heuristic argument.
+    ///
+    /// `heuristic` estimates the remaining cost from a position to `to`.
+    /// It must never overestimate the cost, or the found path might not be the cheapest.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S...\n.##.\n...E\n");
+    /// let to = Point { x: 3, y: 2 };
+    ///
+    /// let outcome = map.astar(
+    ///     Point { x: 0, y: 0 },
+    ///     to,
+    ///     &mut |_map, _pos, dir, tile| (tile != b'#' && dir.is_cardinal()).then_some(1),
+    ///     &mut |pos| pos.manhattan_distance(to),
+    /// );
+    /// assert_eq!(outcome.cost(), Some(5));
+    /// ```
+    pub fn astar<F, H, U>(
+        &self,
+        from: Point<T>,
+        to: Point<T>,
+        f: &mut F,
+        heuristic: &mut H,
+    ) -> SearchOutcome<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        H: FnMut(Point<T>) -> U,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        let outcome = crate::search::astar(
+            from,
+            |&pos| {
+                self.neighbors(pos)
+                    .filter_map(|(next, dir, tile)| {
+                        f(self, next, dir, tile).map(|step| (next, step))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |&pos| pos == to,
+            |&pos| heuristic(pos),
+        );
+        SearchOutcome {
+            path: outcome.path.map(MapPath::from),
+            expanded: outcome.expanded,
+        }
+    }
+
+    /// Search for the cheapest path from `from` to `to` with A*,
+    /// using the manhattan distance to `to` as heuristic.
+    ///
+    /// Only steps in the cardinal directions are taken, even if `f` allows
+    /// diagonal steps, as the manhattan distance overestimates their cost.
+    /// The cost of each step must be at least one for the heuristic to be valid.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S...\n.##.\n...E\n");
+    ///
+    /// let outcome = map.astar_manhattan(
+    ///     Point { x: 0, y: 0 },
+    ///     Point { x: 3, y: 2 },
+    ///     &mut |_map, _pos, dir, tile| (tile != b'#' && dir.is_cardinal()).then_some(1u64),
+    /// );
+    /// assert_eq!(outcome.cost(), Some(5));
+    /// ```
+    pub fn astar_manhattan<F, U>(
+        &self,
+        from: Point<T>,
+        to: Point<T>,
+        f: &mut F,
+    ) -> SearchOutcome<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + NumCast + Ord + Copy + std::fmt::Debug,
+    {
+        let mut cardinal_f = |map: &Self, pos, dir: Dir, tile| {
+            if dir.is_cardinal() {
+                f(map, pos, dir, tile)
+            } else {
+                None
+            }
+        };
+        self.astar(from, to, &mut cardinal_f, &mut |pos| {
+            <U as NumCast>::from(pos.manhattan_distance(to)).expect("Distance fits cost type")
+        })
+    }
+
+    /// Find all the cheapest paths from `from` to `to`.
+    ///
+    /// `f` works as for `search`, but the costs must be positive.
+    ///
+    /// The number of paths is given by `AllPaths::count` and the
+    /// positions on any of them by `AllPaths::states`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S..\n.#.\n..E\n");
+    ///
+    /// let outcome = map.all_shortest_paths(Point { x: 0, y: 0 }, Point { x: 2, y: 2 }, &mut |_map, _pos, dir, tile| {
+    ///     (tile != b'#' && dir.is_cardinal()).then_some(1)
+    /// });
+    /// let paths = outcome.path.expect("Paths");
+    /// assert_eq!(paths.cost, 4);
+    /// assert_eq!(paths.count(), num::BigUint::from(2u32));
+    /// assert_eq!(paths.states().len(), 8);
+    /// ```
+    pub fn all_shortest_paths<F, U>(
+        &self,
+        from: Point<T>,
+        to: Point<T>,
+        f: &mut F,
+    ) -> SearchOutcome<AllPaths<Point<T>, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        crate::search::dijkstra_all(
+            from,
+            |&pos| {
+                self.neighbors(pos)
+                    .filter_map(|(next, dir, tile)| {
+                        f(self, next, dir, tile).map(|step| (next, step))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |&pos| pos == to,
+        )
+    }
+
+    /// Find the distances from `sources` to all positions in the map.
+    ///
+    /// `f` works as for `search`. The distance of a position is the cost of the cheapest path
+    /// from any of the sources to it, or None if it can't be reached.
+    /// Sources outside of the map are ignored.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("a..#.\n.#.#b\n...#.\n");
+    ///
+    /// let field = map.distances(&map.find(b'a'), &mut |_map, _pos, dir, tile| {
+    ///     (tile != b'#' && dir.is_cardinal()).then_some(1)
+    /// });
+    /// assert_eq!(field.get_at(Point { x: 2, y: 2 }), Some(&Some(4)));
+    /// assert_eq!(field.get_at(Point { x: 4, y: 1 }), Some(&None));
+    /// assert_eq!(field.get_at(Point { x: 5, y: 1 }), None);
+    ///
+    /// let within_two = field.iter().filter(|(_pos, distance)| distance.is_some_and(|d| d <= 2));
+    /// assert_eq!(within_two.count(), 5);
+    ///
+    /// let field = map.distances(&[Point { x: -1, y: 0 }], &mut |_map, _pos, _dir, _tile| Some(1));
+    /// assert!(field.iter().all(|(_pos, distance)| distance.is_none()));
+    /// ```
+    pub fn distances<F, U>(&self, sources: &[Point<T>], f: &mut F) -> Grid<Option<U>, T>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        let mut field = Grid::from_map(self, |_| None);
+        let mut to_expand = BinaryHeap::new();
+        for &source in sources.iter().filter(|&&source| self.is_inside_map(source)) {
+            field.set_at(source, Some(Zero::zero()));
+            to_expand.push(PointAndCost {
+                cost: Zero::zero(),
+                point: source,
+            });
+        }
+        while let Some(PointAndCost { cost, point: pos }) = to_expand.pop() {
+            if field
+                .get_at_unchecked(pos)
+                .is_some_and(|old_cost| old_cost < cost)
+            {
+                continue;
+            }
+            for (next, dir, tile) in self.neighbors(pos) {
+                if let Some(step) = f(self, next, dir, tile) {
+                    let new_cost = cost + step;
+                    if field
+                        .get_at_unchecked(next)
+                        .is_none_or(|old_cost| new_cost < old_cost)
+                    {
+                        field.set_at(next, Some(new_cost));
+                        to_expand.push(PointAndCost {
+                            cost: new_cost,
+                            point: next,
+                        });
+                    }
+                }
+            }
+        }
+        field
+    }
+
+    /// Find the distances between all pairs of `points`.
+    ///
+    /// `f` works as for `search`. Element `[i][j]` is the distance from `points[i]`
+    /// to `points[j]`, or None if it can't be reached.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("0.#.2\n..1..\n");
+    /// let points: Vec<_> = (b'0'..=b'2').map(|c| map.find(c)[0]).collect();
+    ///
+    /// let table = map.distance_table(&points, &mut |_map, _pos, dir, tile| {
+    ///     (tile != b'#' && dir.is_cardinal()).then_some(1)
+    /// });
+    /// assert_eq!(table[0][2], Some(6));
+    /// assert_eq!(table[1][1], Some(0));
+    /// ```
+    pub fn distance_table<F, U>(&self, points: &[Point<T>], f: &mut F) -> Vec<Vec<Option<U>>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        points
+            .iter()
+            .map(|&from| {
+                let field = self.distances(&[from], f);
+                points
+                    .iter()
+                    .map(|&to| field.get_at(to).copied().flatten())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Search for the cheapest path from `from` to `to` when turning matters.
+    ///
+    /// The search starts facing `facing`. If it is `Dir::None`,
+    /// the first step can be taken in any direction without a turn cost.
+    /// Only the cardinal directions are used.
+    ///
+    /// `f` works as for `search` and gives the cost of the steps,
+    /// `rules` gives the cost of turning and the limits of straight runs.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("#######\n#....E#\n#.###.#\n#S....#\n#######\n");
+    ///
+    /// let outcome = map.search_turning(
+    ///     Point { x: 1, y: 3 },
+    ///     Dir::East,
+    ///     Point { x: 5, y: 1 },
+    ///     &TurnRules::new(1000),
+    ///     &mut |_map, _pos, _dir, tile| (tile != b'#').then_some(1),
+    /// );
+    /// let path = outcome.path.expect("A path");
+    /// assert_eq!(path.cost, 1006);
+    /// use Dir::*;
+    /// assert_eq!(path.dirs, vec![East, East, East, East, North, North]);
+    /// ```
+    pub fn search_turning<F, U>(
+        &self,
+        from: Point<T>,
+        facing: Dir,
+        to: Point<T>,
+        rules: &TurnRules<U>,
+        f: &mut F,
+    ) -> SearchOutcome<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        let outcome = crate::search::dijkstra(
+            (from, facing, 0),
+            |&(pos, facing, run)| {
+                let mut successors = Vec::with_capacity(4);
+                for dir in CARDINALS {
+                    let can_turn = run == 0 || run >= rules.min_straight;
+                    let turn_cost = if facing == Dir::None || dir == facing {
+                        if run >= rules.max_straight {
+                            continue;
+                        }
+                        Zero::zero()
+                    } else if dir == facing.turn_cardinal_right().turn_cardinal_right() {
+                        if !rules.allow_reverse || !can_turn {
+                            continue;
+                        }
+                        rules.turn_cost + rules.turn_cost
+                    } else {
+                        if !can_turn {
+                            continue;
+                        }
+                        rules.turn_cost
+                    };
+                    let next = pos.walk(dir);
+                    if !self.is_inside_map(next) {
+                        continue;
+                    }
+                    if let Some(step) = f(self, next, dir, self.get_at_unchecked(next)) {
+                        let run = if dir == facing { run + 1 } else { 1 };
+                        // Without a max, longer runs than min_straight behave the same,
+                        // so don't let them become separate states.
+                        let run = if rules.max_straight == usize::MAX {
+                            run.min(rules.min_straight.max(1))
+                        } else {
+                            run
+                        };
+                        successors.push(((next, dir, run), step + turn_cost));
+                    }
+                }
+                successors
+            },
+            |&(pos, _, run)| pos == to && (run == 0 || run >= rules.min_straight),
+        );
+        SearchOutcome {
+            path: outcome.path.map(|path| MapPath {
+                cost: path.cost,
+                points: path.states.iter().map(|&(pos, _, _)| pos).collect(),
+                dirs: path.states[1..].iter().map(|&(_, dir, _)| dir).collect(),
+            }),
+            expanded: outcome.expanded,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CRUCIBLE_MAP: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    fn crucible(min_straight: usize, max_straight: usize) -> Option<u32> {
+        let map = Map::<i32>::from_string(CRUCIBLE_MAP);
+        let rules = TurnRules {
+            turn_cost: 0,
+            min_straight,
+            max_straight,
+            allow_reverse: false,
+        };
+        map.search_turning(
+            Point { x: 0, y: 0 },
+            Dir::None,
+            Point {
+                x: map.get_width() - 1,
+                y: map.get_height() - 1,
+            },
+            &rules,
+            &mut |_map, _pos, _dir, tile| Some((tile - b'0') as u32),
+        )
+        .cost()
+    }
+
+    #[test]
+    fn test_search_turning_max_straight() {
+        assert_eq!(crucible(0, 3), Some(102));
+    }
+
+    #[test]
+    fn test_search_turning_min_straight() {
+        assert_eq!(crucible(4, 10), Some(94));
+    }
+
+    #[test]
+    fn test_search_turning_unlimited_runs_are_not_states() {
+        let mut map = Map::<i32>::new(60, 60);
+        let goal = Point { x: 59, y: 59 };
+        map.set_at(goal, b'#');
+        let outcome = map.search_turning(
+            Point { x: 0, y: 0 },
+            Dir::East,
+            goal,
+            &TurnRules::new(1000),
+            &mut |_map, _pos, _dir, tile| (tile != b'#').then_some(1),
+        );
+        assert!(!outcome.is_reachable());
+        assert!(outcome.expanded <= 4 * 60 * 60 + 1);
+    }
+
+    #[test]
+    fn test_astar_manhattan_ignores_diagonal_steps() {
+        // A simple linear congruential generator, to get the same maps every time.
+        let mut seed = 12345u32;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed >> 16
+        };
+        for _ in 0..200 {
+            let mut map = Map::<i32>::new(8, 8);
+            for y in 0..8 {
+                for x in 0..8 {
+                    if random() % 4 == 0 {
+                        map.set_at(Point { x, y }, b'#');
+                    }
+                }
+            }
+            let from = Point { x: 0, y: 0 };
+            let to = Point { x: 7, y: 7 };
+            map.set_at(from, b'.');
+            map.set_at(to, b'.');
+
+            let any_dir = &mut |_: &Map<i32>, _, _, tile| (tile != b'#').then_some(1u32);
+            let cardinal = &mut |_: &Map<i32>, _, dir: Dir, tile| {
+                (tile != b'#' && dir.is_cardinal()).then_some(1u32)
+            };
+            assert_eq!(
+                map.astar_manhattan(from, to, any_dir).cost(),
+                map.search(from, to, cardinal).cost()
+            );
+        }
+    }
+}
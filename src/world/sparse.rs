@@ -0,0 +1,293 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, Length, LengthType, Map, Point};
+use num::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// SparseMap stores ASCII tiles without any fixed dimensions.
+///
+/// All positions not set hold the default tile.
+/// Only the tiles that differ from the default tile are stored.
+///
+/// # Example
+///
+/// ```
+/// # use advent_of_tools::*;
+/// let mut map = SparseMap::<i32>::new(b'.');
+///
+/// map.set_at(Point { x: -5, y: 3 }, b'#');
+/// map.set_at(Point { x: 7, y: -1 }, b'#');
+/// assert_eq!(map.get_at(Point { x: 100, y: 100 }), b'.');
+/// assert_eq!(map.bounds(), Some((Point { x: -5, y: -1 }, Point { x: 7, y: 3 })));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SparseMap<T: LengthType = Length> {
+    tiles: HashMap<Point<T>, u8>,
+    default: u8,
+    /// The number of stored tiles in each row.
+    rows: BTreeMap<T, usize>,
+    /// The number of stored tiles in each column.
+    columns: BTreeMap<T, usize>,
+}
+
+/// Iterator over the neighbors of a position in a SparseMap.
+pub struct SparseNeighborIterator<'a, T: LengthType> {
+    map: &'a SparseMap<T>,
+    pos: Point<T>,
+    dir: Dir,
+}
+
+impl<T: LengthType> Iterator for SparseNeighborIterator<'_, T> {
+    type Item = (Point<T>, Dir, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.dir == Dir::None {
+            return None;
+        }
+        let dir = self.dir;
+        self.dir = self.dir.turn_right();
+        if self.dir == Dir::North {
+            self.dir = Dir::None;
+        }
+        let pos = self.pos.walk(dir);
+        Some((pos, dir, self.map.get_at(pos)))
+    }
+}
+
+impl<T: LengthType> SparseMap<T> {
+    /// Create a new empty map where all positions hold `default`.
+    pub fn new(default: u8) -> Self {
+        Self {
+            tiles: HashMap::new(),
+            default,
+            rows: BTreeMap::new(),
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// Get the default tile.
+    pub fn get_default(&self) -> u8 {
+        self.default
+    }
+
+    /// Get the tile at a position.
+    pub fn get_at(&self, pos: Point<T>) -> u8 {
+        self.tiles.get(&pos).copied().unwrap_or(self.default)
+    }
+
+    /// Set the tile at a position.
+    ///
+    /// Setting the default tile removes the position from the map.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = SparseMap::<i32>::new(b'.');
+    /// map.set_at(Point { x: 0, y: 0 }, b'#');
+    /// map.set_at(Point { x: 0, y: 0 }, b'.');
+    /// map.set_at(Point { x: 5, y: 5 }, b'#');
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map.bounds(), Some((Point { x: 5, y: 5 }, Point { x: 5, y: 5 })));
+    /// ```
+    pub fn set_at(&mut self, pos: Point<T>, val: u8) {
+        if val == self.default {
+            if self.tiles.remove(&pos).is_some() {
+                Self::uncount(&mut self.rows, pos.y);
+                Self::uncount(&mut self.columns, pos.x);
+            }
+        } else if self.tiles.insert(pos, val).is_none() {
+            *self.rows.entry(pos.y).or_default() += 1;
+            *self.columns.entry(pos.x).or_default() += 1;
+        }
+    }
+
+    /// Count one less tile in a row or column.
+    fn uncount(counts: &mut BTreeMap<T, usize>, key: T) {
+        let count = counts.get_mut(&key).expect("A counted tile");
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&key);
+        }
+    }
+
+    /// Get the bounding box of the tiles that don't hold the default tile.
+    ///
+    /// Returns the top left and bottom right corners, both inclusive,
+    /// or None if all positions hold the default tile.
+    pub fn bounds(&self) -> Option<(Point<T>, Point<T>)> {
+        let (&min_x, _) = self.columns.first_key_value()?;
+        let (&max_x, _) = self.columns.last_key_value()?;
+        let (&min_y, _) = self.rows.first_key_value()?;
+        let (&max_y, _) = self.rows.last_key_value()?;
+        Some((Point { x: min_x, y: min_y }, Point { x: max_x, y: max_y }))
+    }
+
+    /// Returns the number of positions that don't hold the default tile.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns true if all positions hold the default tile.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Iterate over all positions that don't hold the default tile.
+    ///
+    /// The iterator returns a tuple of the position's Point and the tile,
+    /// in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<T>, u8)> + '_ {
+        self.tiles.iter().map(|(&pos, &tile)| (pos, tile))
+    }
+
+    /// Iterate over all 8 neigbors to a position.
+    ///
+    /// The iterator returns a tuple of the neighbor's Point,
+    /// the direction to it and the tile.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = SparseMap::<i32>::new(b'.');
+    /// map.set_at(Point { x: 1, y: 1 }, b'#');
+    ///
+    /// let walls = map.neighbors(Point { x: 0, y: 0 }).filter(|&(_, _, tile)| tile == b'#');
+    /// assert_eq!(walls.map(|(_, dir, _)| dir).collect::<Vec<_>>(), vec![Dir::SouthEast]);
+    /// ```
+    pub fn neighbors(&self, pos: Point<T>) -> SparseNeighborIterator<'_, T> {
+        SparseNeighborIterator {
+            map: self,
+            pos,
+            dir: Dir::North,
+        }
+    }
+
+    /// Print the bounding box of the map to stdout with an overlay provided by f.
+    ///
+    /// Works like `Map::print_with_overlay`.
+    pub fn print_with_overlay<F>(&self, mut f: F)
+    where
+        F: FnMut(Point<T>, u8) -> u8,
+    {
+        if let Some((min, max)) = self.bounds() {
+            for y in range_inclusive(min.y, max.y) {
+                for x in range_inclusive(min.x, max.x) {
+                    let pos = Point { x, y };
+                    print!("{}", char::from(f(pos, self.get_at(pos))));
+                }
+                println!();
+            }
+        }
+    }
+
+    /// Print the bounding box of the map to stdout.
+    pub fn print(&self) {
+        self.print_with_overlay(|_, tile| tile);
+    }
+
+    /// Create a SparseMap from a Map.
+    ///
+    /// The Map's positions are kept and its tiles equal to `default` are not stored.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("..#\n#..\n");
+    /// let sparse = SparseMap::from_map(&map, b'.');
+    ///
+    /// assert_eq!(sparse.len(), 2);
+    /// assert_eq!(sparse.get_at(Point { x: 0, y: 1 }), b'#');
+    /// ```
+    pub fn from_map(map: &Map<T>, default: u8) -> Self
+    where
+        usize: TryFrom<T>,
+        <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    {
+        let mut sparse = Self::new(default);
+        for (pos, tile) in map {
+            sparse.set_at(pos, tile);
+        }
+        sparse
+    }
+
+    /// Create a Map of the bounding box of this map.
+    ///
+    /// The Map's top left corner is the bounding box's top left corner,
+    /// which is returned together with the Map.
+    /// Returns None if the SparseMap is empty, as it has no bounding box.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut sparse = SparseMap::<i32>::new(b'.');
+    /// assert_eq!(sparse.to_map(), None);
+    ///
+    /// sparse.set_at(Point { x: -1, y: 4 }, b'#');
+    /// sparse.set_at(Point { x: 1, y: 5 }, b'@');
+    ///
+    /// let (map, offset) = sparse.to_map().expect("A non-empty map");
+    /// assert_eq!(offset, Point { x: -1, y: 4 });
+    /// assert_eq!(map.get_width(), 3);
+    /// assert_eq!(map.get_height(), 2);
+    /// assert_eq!(map.find(b'@'), vec![Point { x: 2, y: 1 }]);
+    /// ```
+    pub fn to_map(&self) -> Option<(Map<T>, Point<T>)>
+    where
+        usize: TryFrom<T>,
+        <usize as TryFrom<T>>::Error: std::fmt::Debug,
+    {
+        let (min, max) = self.bounds()?;
+        let width = max.x - min.x + One::one();
+        let height = max.y - min.y + One::one();
+        let mut map = Map {
+            data: vec![self.default; usize::try_from(width * height).expect("Positive size")],
+            width,
+            height,
+            has_border: false,
+        };
+        for (&pos, &tile) in &self.tiles {
+            map.set_at(
+                Point {
+                    x: pos.x - min.x,
+                    y: pos.y - min.y,
+                },
+                tile,
+            );
+        }
+        Some((map, min))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bounds_follow_moved_tiles() {
+        let mut sparse = SparseMap::<i32>::new(b'.');
+        sparse.set_at(Point { x: 0, y: 0 }, b'#');
+        sparse.set_at(Point { x: 3, y: 1 }, b'#');
+        sparse.set_at(Point { x: 3, y: 1 }, b'@');
+
+        // Move the tile in the corner.
+        sparse.set_at(Point { x: 0, y: 0 }, b'.');
+        sparse.set_at(Point { x: 2, y: 2 }, b'#');
+        assert_eq!(
+            sparse.bounds(),
+            Some((Point { x: 2, y: 1 }, Point { x: 3, y: 2 }))
+        );
+        let (map, offset) = sparse.to_map().unwrap();
+        assert_eq!(offset, Point { x: 2, y: 1 });
+        assert_eq!(map, Map::from_string(".@\n#.\n"));
+
+        sparse.set_at(Point { x: 3, y: 1 }, b'.');
+        sparse.set_at(Point { x: 2, y: 2 }, b'.');
+        assert_eq!(sparse.bounds(), None);
+        assert!(sparse.is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{LengthType, Map, Point};
+use num::*;
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the area of the map inside its border.
+    ///
+    /// `from` is the top left corner of the area,
+    /// `to` is the bottom right corner of the area, just like for `transform_area`.
+    /// If the map has no border, the area is the whole map.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string_with_border("abc\ndef\n");
+    ///
+    /// assert_eq!(map.inner_area(), (Point { x: 1, y: 1 }, Point { x: 4, y: 3 }));
+    /// ```
+    pub fn inner_area(&self) -> (Point<T>, Point<T>) {
+        if self.has_border {
+            (
+                Point {
+                    x: One::one(),
+                    y: One::one(),
+                },
+                Point {
+                    x: self.width - One::one(),
+                    y: self.height - One::one(),
+                },
+            )
+        } else {
+            (
+                Point {
+                    x: Zero::zero(),
+                    y: Zero::zero(),
+                },
+                Point {
+                    x: self.width,
+                    y: self.height,
+                },
+            )
+        }
+    }
+
+    /// Copy an area of the map into a new map.
+    ///
+    /// `from` is the top left corner of the area,
+    /// `to` is the bottom right corner of the area, just like for `transform_area`.
+    ///
+    /// Panics if the area is not within the map.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("abcd\nefgh\nijkl\n");
+    ///
+    /// let sub_map = map.sub_map(Point { x: 1, y: 1 }, Point { x: 3, y: 3 });
+    /// assert_eq!(sub_map, Map::from_string("fg\njk\n"));
+    /// ```
+    pub fn sub_map(&self, from: Point<T>, to: Point<T>) -> Self {
+        let zero: T = Zero::zero();
+        assert!(
+            zero <= from.x
+                && from.x <= to.x
+                && to.x <= self.width
+                && zero <= from.y
+                && from.y <= to.y
+                && to.y <= self.height,
+            "The area {from:?} to {to:?} is not within the {:?}x{:?} map",
+            self.width,
+            self.height
+        );
+        let width = to.x - from.x;
+        let height = to.y - from.y;
+        let mut data = Vec::with_capacity(usize::try_from(width * height).expect("Positive size"));
+        for y in range(from.y, to.y) {
+            let start = self.get_index_for(Point { x: from.x, y });
+            let end = self.get_index_for(Point { x: to.x, y });
+            data.extend_from_slice(&self.data[start..end]);
+        }
+        Self {
+            data,
+            width,
+            height,
+            has_border: false,
+        }
+    }
+
+    /// Returns a copy of the map without its border.
+    ///
+    /// A map without a border is just cloned.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string_with_border("abc\ndef\n");
+    ///
+    /// assert_eq!(map.without_border(), Map::from_string("abc\ndef\n"));
+    /// ```
+    pub fn without_border(&self) -> Self {
+        let (from, to) = self.inner_area();
+        self.sub_map(from, to)
+    }
+
+    /// Crop the map to the smallest area holding all tiles that aren't `background`.
+    ///
+    /// The border is not part of the cropped map.
+    /// If all tiles are `background`, the cropped map is empty.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string(".....\n..#..\n...#.\n.....\n");
+    ///
+    /// assert_eq!(map.crop(b'.'), Map::from_string("#.\n.#\n"));
+    /// ```
+    pub fn crop(&self, background: u8) -> Self {
+        let (from, to) = self.inner_area();
+        let mut bounds: Option<(Point<T>, Point<T>)> = None;
+        for (pos, tile) in self.iter() {
+            if tile == background
+                || pos.x < from.x
+                || pos.y < from.y
+                || pos.x >= to.x
+                || pos.y >= to.y
+            {
+                continue;
+            }
+            let end = Point {
+                x: pos.x + One::one(),
+                y: pos.y + One::one(),
+            };
+            bounds = Some(match bounds {
+                None => (pos, end),
+                Some((min, max)) => (
+                    Point {
+                        x: min.x.min(pos.x),
+                        y: min.y.min(pos.y),
+                    },
+                    Point {
+                        x: max.x.max(end.x),
+                        y: max.y.max(end.y),
+                    },
+                ),
+            });
+        }
+        match bounds {
+            Some((min, max)) => self.sub_map(min, max),
+            None => self.sub_map(from, from),
+        }
+    }
+
+    /// Copy another map onto this map with its top left corner at `offset`.
+    ///
+    /// Tiles equal to `transparent` are not copied,
+    /// neither are tiles that end up outside of this map.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = Map::<i32>::new(4, 3);
+    /// let stamp = Map::from_string("#.\n##\n");
+    ///
+    /// map.paste(&stamp, Point { x: 2, y: 0 }, Some(b'.'));
+    /// map.paste(&stamp, Point { x: -1, y: 1 }, None);
+    /// assert_eq!(map, Map::from_string("..#.\n..##\n#...\n"));
+    /// ```
+    pub fn paste(&mut self, other: &Self, offset: Point<T>, transparent: Option<u8>) {
+        for (pos, tile) in other.iter() {
+            if Some(tile) == transparent {
+                continue;
+            }
+            let pos = Point {
+                x: pos.x + offset.x,
+                y: pos.y + offset.y,
+            };
+            if self.is_inside_map(pos) {
+                self.set_at(pos, tile);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "is not within the 2x2 map")]
+    fn test_sub_map_past_right_edge() {
+        let map = Map::<i32>::from_string("ab\ncd\n");
+        map.sub_map(Point { x: 1, y: 0 }, Point { x: 3, y: 1 });
+    }
+}
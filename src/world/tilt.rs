@@ -0,0 +1,146 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, LengthType, Map, Point};
+use num::*;
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Slide all `movable` tiles in direction `dir` as far as they can go.
+    ///
+    /// The tiles move over `empty` tiles and stop at any other tile,
+    /// at other stopped `movable` tiles or at the edge of the map.
+    /// The border, if any, is not changed.
+    ///
+    /// Returns the number of tiles that moved.
+    ///
+    /// Panics if `dir` is not a cardinal direction.
+    ///
+    /// # Example:
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let mut map = Map::<i32>::from_string(".O.\nO#.\n.OO\n");
+    ///
+    /// assert_eq!(map.tilt(Dir::North, b'O', b'.'), 2);
+    /// assert_eq!(map, Map::from_string("OOO\n.#.\n.O.\n"));
+    ///
+    /// assert_eq!(map.tilt(Dir::South, b'O', b'.'), 2);
+    /// assert_eq!(map, Map::from_string(".O.\n.#.\nOOO\n"));
+    /// ```
+    pub fn tilt(&mut self, dir: Dir, movable: u8, empty: u8) -> usize {
+        let (from, to) = self.inner_area();
+        let one: T = One::one();
+        // The first position of each row or column, in the direction the tiles end up,
+        // and the direction to scan them in.
+        let (starts, scan): (Vec<Point<T>>, Dir) = match dir {
+            Dir::North => (
+                range(from.x, to.x)
+                    .map(|x| Point { x, y: from.y })
+                    .collect(),
+                Dir::South,
+            ),
+            Dir::South => (
+                range(from.x, to.x)
+                    .map(|x| Point { x, y: to.y - one })
+                    .collect(),
+                Dir::North,
+            ),
+            Dir::West => (
+                range(from.y, to.y)
+                    .map(|y| Point { x: from.x, y })
+                    .collect(),
+                Dir::East,
+            ),
+            Dir::East => (
+                range(from.y, to.y)
+                    .map(|y| Point { x: to.x - one, y })
+                    .collect(),
+                Dir::West,
+            ),
+            _ => panic!("Can only tilt in cardinal directions, not {dir}"),
+        };
+        let inside =
+            |pos: Point<T>| pos.x >= from.x && pos.y >= from.y && pos.x < to.x && pos.y < to.y;
+
+        let mut moved = 0;
+        for start in starts {
+            let mut free = start;
+            let mut pos = start;
+            while inside(pos) {
+                let tile = self.get_at_unchecked(pos);
+                if tile == movable {
+                    if pos != free {
+                        self.set_at(free, movable);
+                        self.set_at(pos, empty);
+                        moved += 1;
+                    }
+                    free = free.walk(scan);
+                } else if tile != empty {
+                    free = pos.walk(scan);
+                }
+                pos = pos.walk(scan);
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::find_cycle;
+
+    const PLATFORM: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    fn load(map: &Map<i32>) -> i32 {
+        map.find(b'O')
+            .iter()
+            .map(|pos| map.get_height() - pos.y)
+            .sum()
+    }
+
+    fn spin_cycle(map: &Map<i32>) -> Map<i32> {
+        let mut map = map.clone();
+        for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+            map.tilt(dir, b'O', b'.');
+        }
+        map
+    }
+
+    #[test]
+    fn test_tilt_north_load() {
+        let mut map = Map::from_string(PLATFORM);
+        map.tilt(Dir::North, b'O', b'.');
+        assert_eq!(load(&map), 136);
+    }
+
+    #[test]
+    fn test_spin_cycles() {
+        let history = find_cycle(Map::from_string(PLATFORM), spin_cycle);
+        assert_eq!(load(history.state_at(1_000_000_000)), 64);
+    }
+
+    #[test]
+    fn test_tilt_keeps_border() {
+        let mut map = Map::<i32>::from_string_with_border(".O\nO.\n");
+        assert_eq!(map.tilt(Dir::West, b'O', b'.'), 1);
+        assert_eq!(map, Map::from_string_with_border("O.\nO.\n"));
+    }
+}
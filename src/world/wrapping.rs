@@ -0,0 +1,244 @@
+// SPDX-FileCopyrightText: 2026 Sebastian Andersson <sebastian@bittr.nu>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+#![warn(missing_docs)]
+
+use super::{Dir, LengthType, Map, MapPath, Point, PointAndCost};
+use crate::search::SearchOutcome;
+use num::*;
+use std::collections::{BinaryHeap, HashMap};
+
+/// A view of a Map that repeats infinitely in every direction.
+///
+/// Every Point is valid, it is mapped back into the Map.
+/// If the map has a border, it is repeated as well.
+///
+/// Created by `Map::wrapping`.
+///
+/// # Example
+///
+/// ```
+/// # use advent_of_tools::*;
+/// let map = Map::<i32>::from_string("ab\ncd\n");
+/// let infinite = map.wrapping();
+///
+/// assert_eq!(infinite.get_at(Point { x: -1, y: 5 }), b'd');
+/// assert_eq!(infinite.tile_of(Point { x: -1, y: 5 }), Point { x: -1, y: 2 });
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct WrappingMap<'a, T: LengthType>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    map: &'a Map<T>,
+}
+
+/// Iterator over the neighbors of a position in a WrappingMap.
+pub struct WrappingNeighborIterator<'a, T: LengthType>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    map: WrappingMap<'a, T>,
+    pos: Point<T>,
+    dir: Dir,
+}
+
+impl<T: LengthType> Iterator for WrappingNeighborIterator<'_, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    type Item = (Point<T>, Dir, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.dir == Dir::None {
+            return None;
+        }
+        let dir = self.dir;
+        self.dir = self.dir.turn_right();
+        if self.dir == Dir::North {
+            self.dir = Dir::None;
+        }
+        let pos = self.pos.walk(dir);
+        Some((pos, dir, self.map.get_at(pos)))
+    }
+}
+
+fn rem_euclid<T: LengthType>(a: T, b: T) -> T {
+    let r = a % b;
+    if r < Zero::zero() {
+        r + b
+    } else {
+        r
+    }
+}
+
+impl<'a, T: LengthType> WrappingMap<'a, T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get the underlying Map.
+    pub fn map(&self) -> &'a Map<T> {
+        self.map
+    }
+
+    /// Get the position in the underlying Map that `pos` corresponds to.
+    pub fn wrap(&self, pos: Point<T>) -> Point<T> {
+        Point {
+            x: rem_euclid(pos.x, self.map.get_width()),
+            y: rem_euclid(pos.y, self.map.get_height()),
+        }
+    }
+
+    /// Get which copy of the Map `pos` is in.
+    ///
+    /// The original Map is at `Point { x: 0, y: 0 }`, the copy to the left of it
+    /// at `Point { x: -1, y: 0 }` etc.
+    pub fn tile_of(&self, pos: Point<T>) -> Point<T> {
+        let wrapped = self.wrap(pos);
+        Point {
+            x: (pos.x - wrapped.x) / self.map.get_width(),
+            y: (pos.y - wrapped.y) / self.map.get_height(),
+        }
+    }
+
+    /// Get the tile at any position.
+    pub fn get_at(&self, pos: Point<T>) -> u8 {
+        self.map.get_at_unchecked(self.wrap(pos))
+    }
+
+    /// Iterate over all 8 neigbors to a position.
+    ///
+    /// The iterator returns a tuple of the neighbor's Point,
+    /// the direction to it and the tile.
+    pub fn neighbors(&self, pos: Point<T>) -> WrappingNeighborIterator<'a, T> {
+        WrappingNeighborIterator {
+            map: *self,
+            pos,
+            dir: Dir::North,
+        }
+    }
+
+    /// Search for the cheapest path from `from` to `to` that costs at most `max`.
+    ///
+    /// Works like `Map::search`, but `f` is called with this view and
+    /// positions outside of the underlying Map.
+    ///
+    /// As the map is infinite, positions more than `max` away are never searched,
+    /// so the search also ends if `to` can't be reached.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("S#.\n##.\n...\n");
+    /// let mut f = |_map: &WrappingMap<i32>, _pos, dir: Dir, tile| (tile != b'#' && dir.is_cardinal()).then_some(1);
+    ///
+    /// let outcome = map.wrapping().search(Point { x: 0, y: 0 }, Point { x: 2, y: 0 }, 100, &mut f);
+    /// assert_eq!(outcome.cost(), Some(4));
+    ///
+    /// let outcome = map.wrapping().search(Point { x: 0, y: 0 }, Point { x: 1, y: 0 }, 100, &mut f);
+    /// assert!(!outcome.is_reachable());
+    /// ```
+    pub fn search<F, U>(
+        &self,
+        from: Point<T>,
+        to: Point<T>,
+        max: U,
+        f: &mut F,
+    ) -> SearchOutcome<MapPath<T, U>>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        let outcome = crate::search::dijkstra_within(
+            from,
+            |&pos| {
+                self.neighbors(pos)
+                    .filter_map(|(next, dir, tile)| {
+                        f(self, next, dir, tile).map(|step| (next, step))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |&pos| pos == to,
+            max,
+        );
+        SearchOutcome {
+            path: outcome.path.map(MapPath::from),
+            expanded: outcome.expanded,
+        }
+    }
+
+    /// Find the distances from `sources` to all positions at most `max` away.
+    ///
+    /// `f` works as for `search`.
+    ///
+    /// # Example
+    /// ```
+    /// # use advent_of_tools::*;
+    /// let map = Map::<i32>::from_string("...\n.#.\n...\n");
+    ///
+    /// let distances = map.wrapping().distances_within(&[Point { x: 0, y: 0 }], 10, &mut |_map, _pos, dir, tile| {
+    ///     (tile != b'#' && dir.is_cardinal()).then_some(1)
+    /// });
+    /// assert_eq!(distances.get(&Point { x: -10, y: 0 }), Some(&10));
+    /// assert_eq!(distances.get(&Point { x: -20, y: 0 }), None);
+    /// assert_eq!(distances.get(&Point { x: 4, y: -2 }), None);
+    /// ```
+    pub fn distances_within<F, U>(
+        &self,
+        sources: &[Point<T>],
+        max: U,
+        f: &mut F,
+    ) -> HashMap<Point<T>, U>
+    where
+        F: FnMut(&Self, Point<T>, Dir, u8) -> Option<U>,
+        U: Num + Ord + Copy + std::fmt::Debug,
+    {
+        let mut distances = HashMap::new();
+        let mut to_expand = BinaryHeap::new();
+        for &source in sources {
+            distances.insert(source, Zero::zero());
+            to_expand.push(PointAndCost {
+                cost: Zero::zero(),
+                point: source,
+            });
+        }
+        while let Some(PointAndCost { cost, point: pos }) = to_expand.pop() {
+            if distances.get(&pos).is_some_and(|&old_cost| old_cost < cost) {
+                continue;
+            }
+            for (next, dir, tile) in self.neighbors(pos) {
+                if let Some(step) = f(self, next, dir, tile) {
+                    let new_cost = cost + step;
+                    if new_cost <= max
+                        && distances
+                            .get(&next)
+                            .is_none_or(|&old_cost| new_cost < old_cost)
+                    {
+                        distances.insert(next, new_cost);
+                        to_expand.push(PointAndCost {
+                            cost: new_cost,
+                            point: next,
+                        });
+                    }
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl<T: LengthType> Map<T>
+where
+    usize: TryFrom<T>,
+    <usize as TryFrom<T>>::Error: std::fmt::Debug,
+{
+    /// Get a view of the map that repeats infinitely in every direction.
+    pub fn wrapping(&self) -> WrappingMap<'_, T> {
+        WrappingMap { map: self }
+    }
+}